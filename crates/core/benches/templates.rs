@@ -0,0 +1,69 @@
+// Compares rendering of problems with the templates parsed once per type
+// to parsing the templates anew for every problem.
+use core::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+use macros::IntoContext;
+
+#[derive(Debug, Deserialize, FromRow, IntoContext)]
+pub struct ColumnMissed {
+    pub table_name: String,
+    pub column_name: String,
+}
+
+impl CustomProblem for ColumnMissed {
+    type Client = PostgresClient;
+
+    fn kind_() -> &'static str {
+        "ColumnMissed"
+    }
+    fn message_() -> &'static str {
+        "The column {{ table_name }}.{{ column_name }} is missed"
+    }
+    fn migration_() -> Option<&'static str> {
+        Some("ALTER TABLE {{ table_name }} ADD COLUMN {{ column_name }} text;")
+    }
+    fn rollback_() -> Option<&'static str> {
+        Some("ALTER TABLE {{ table_name }} DROP COLUMN {{ column_name }};")
+    }
+}
+
+fn problems() -> Vec<ColumnMissed> {
+    (0..1000)
+        .map(|i| ColumnMissed {
+            table_name: format!("table_{}", i % 50),
+            column_name: format!("column_{}", i),
+        })
+        .collect()
+}
+
+fn render(c: &mut Criterion) {
+    let problems = problems();
+    let mut group = c.benchmark_group("render 1000 problems");
+    group.bench_function("cached", |b| {
+        b.iter(|| {
+            for problem in &problems {
+                problem.message().unwrap();
+                problem.migration().unwrap().unwrap();
+                problem.rollback().unwrap().unwrap();
+            }
+        })
+    });
+    group.bench_function("uncached", |b| {
+        b.iter(|| {
+            for problem in &problems {
+                let context = Context::from(problem);
+                for template in [
+                    ColumnMissed::message_(),
+                    ColumnMissed::migration_().unwrap(),
+                    ColumnMissed::rollback_().unwrap(),
+                ] {
+                    Tera::one_off(template, &context, false).unwrap();
+                }
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, render);
+criterion_main!(benches);
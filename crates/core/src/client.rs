@@ -1,5 +1,6 @@
 use std::error::Error as StdError;
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::future::Future;
 
 #[cfg(feature = "postgres")]
 use postgres::{Error as PostgresError, Row as PostgresRow};
@@ -24,7 +25,45 @@ impl<T: FromRow> TryFromRow<PostgresRow> for T {
 /// Interface to interact with a database
 pub trait Client {
     type Row;
+    /// The name of the client matching the `client` of problem definitions.
+    /// Clients without a name (the default) run inspectors of any problem.
+    const NAME: &'static str = "";
+
     fn query(&mut self, query: &str) -> Result<Vec<Self::Row>, ExecuteQueryError>;
+
+    /// Check that the database is reachable.
+    fn ping(&mut self) -> Result<(), ExecuteQueryError> {
+        self.query("SELECT 1").map(|_| ())
+    }
+}
+
+/// Interface to interact with a database without blocking the executor
+pub trait AsyncClient {
+    type Row;
+    fn query(
+        &mut self,
+        query: &str,
+    ) -> impl Future<Output = Result<Vec<Self::Row>, ExecuteQueryError>>;
+}
+
+/// Source of independent clients to run inspectors in parallel.
+///
+/// Any function establishing a new connection is a pool as well,
+/// e.g. `|| PostgresClient::connect(url)`.
+pub trait Pool: Sync {
+    type Client: Client;
+    fn get(&self) -> Result<Self::Client, EstablishConnectionError>;
+}
+
+impl<C: Client, F> Pool for F
+where
+    F: Fn() -> Result<C, EstablishConnectionError> + Sync,
+{
+    type Client = C;
+
+    fn get(&self) -> Result<C, EstablishConnectionError> {
+        self()
+    }
 }
 
 #[derive(Debug)]
@@ -117,6 +156,7 @@ impl PostgresClient {
 #[cfg(feature = "postgres")]
 impl Client for PostgresClient {
     type Row = PostgresRow;
+    const NAME: &'static str = "PostgresClient";
 
     fn query(&mut self, query: &str) -> Result<Vec<Self::Row>, ExecuteQueryError> {
         self.conn
@@ -1,8 +1,13 @@
 use serde_json::Error as JsonError;
+use serde_yaml::Error as YamlError;
 use std::error::Error as StdError;
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::Error as IoError;
+use std::path::PathBuf;
 use std::result::Result as StdResult;
 use tera::Error as TeraError;
+use toml::de::Error as TomlDeError;
+use toml::ser::Error as TomlSerError;
 
 use crate::client::{EstablishConnectionError, ExecuteQueryError, ParseRowError};
 use crate::to_sql::Error as ToSqlError;
@@ -13,11 +18,21 @@ pub type Result<T> = StdResult<T, Error>;
 pub enum Error {
     EstablishConnection(EstablishConnectionError),
     ExecuteQuery(ExecuteQueryError),
+    MissingRule(String),
     ParseConfig(JsonError),
     ParseRow(ParseRowError),
+    ParseToml(TomlDeError),
+    ParseYaml(YamlError),
+    PingDatabase(ExecuteQueryError),
+    ReadConfig(IoError),
+    ReadFile(PathBuf, IoError),
     RenderSql(ToSqlError),
     RenderTemplate(&'static str, TeraError),
-    UnknownProblem(String),
+    SerializeJson(JsonError),
+    SerializeToml(TomlSerError),
+    UnknownInspector(String),
+    UnsupportedFormat(PathBuf),
+    WriteFile(PathBuf, IoError),
 }
 
 impl Display for Error {
@@ -25,11 +40,21 @@ impl Display for Error {
         match self {
             Self::EstablishConnection(err) => write!(f, "Failed to establish connection: {}", err),
             Self::ExecuteQuery(err) => write!(f, "Failed to execute query: {}", err),
+            Self::MissingRule(key) => write!(f, "Rule is not configured: {}", key),
             Self::ParseConfig(err) => write!(f, "Failed to parse JSON: {}", err),
             Self::ParseRow(err) => write!(f, "Failed to parse row: {}", err),
+            Self::ParseToml(err) => write!(f, "Failed to parse TOML: {}", err),
+            Self::ParseYaml(err) => write!(f, "Failed to parse YAML: {}", err),
+            Self::PingDatabase(err) => write!(f, "Database is unreachable: {}", err),
+            Self::ReadConfig(err) => write!(f, "Failed to read config: {}", err),
+            Self::ReadFile(path, err) => write!(f, "Failed to read {:?}: {}", path, err),
             Self::RenderSql(err) => write!(f, "Failed to render SQL WHERE clause: {}", err),
             Self::RenderTemplate(kind, err) => write!(f, "Failed to render {}: {}", kind, err),
-            Self::UnknownProblem(key) => write!(f, "Unknown problem: {}", key),
+            Self::SerializeJson(err) => write!(f, "Failed to serialize JSON: {}", err),
+            Self::SerializeToml(err) => write!(f, "Failed to serialize TOML: {}", err),
+            Self::UnknownInspector(key) => write!(f, "Unknown inspector: {}", key),
+            Self::UnsupportedFormat(path) => write!(f, "Unsupported config format: {:?}", path),
+            Self::WriteFile(path, err) => write!(f, "Failed to write {:?}: {}", path, err),
         }
     }
 }
@@ -41,8 +66,16 @@ impl StdError for Error {
             Self::ExecuteQuery(err) => Some(err),
             Self::ParseConfig(err) => Some(err),
             Self::ParseRow(err) => Some(err),
+            Self::ParseToml(err) => Some(err),
+            Self::ParseYaml(err) => Some(err),
+            Self::PingDatabase(err) => Some(err),
+            Self::ReadConfig(err) => Some(err),
+            Self::ReadFile(_, err) => Some(err),
             Self::RenderSql(err) => Some(err),
             Self::RenderTemplate(_, err) => Some(err),
+            Self::SerializeJson(err) => Some(err),
+            Self::SerializeToml(err) => Some(err),
+            Self::WriteFile(_, err) => Some(err),
             _ => None,
         }
     }
@@ -78,14 +111,119 @@ impl From<ParseRowError> for Error {
     }
 }
 
+impl From<TomlDeError> for Error {
+    fn from(err: TomlDeError) -> Self {
+        Self::ParseToml(err)
+    }
+}
+
+impl From<YamlError> for Error {
+    fn from(err: YamlError) -> Self {
+        Self::ParseYaml(err)
+    }
+}
+
 impl From<(&'static str, TeraError)> for Error {
     fn from((kind, err): (&'static str, TeraError)) -> Self {
         Self::RenderTemplate(kind, err)
     }
 }
 
+// Used by `Inspector::build` to reject an unknown key
 impl From<String> for Error {
     fn from(key: String) -> Self {
-        Self::UnknownProblem(key)
+        Self::UnknownInspector(key)
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod test {
+    use super::*;
+    use postgres::{Config, Error as PostgresError};
+
+    fn postgres_error() -> PostgresError {
+        "unknown_option=1".parse::<Config>().unwrap_err()
+    }
+
+    #[test]
+    fn from_establish_connection_error() {
+        let err: Error = EstablishConnectionError::Postgres(postgres_error()).into();
+
+        assert!(matches!(err, Error::EstablishConnection(_)));
+        assert!(err
+            .to_string()
+            .starts_with("Failed to establish connection: "));
+    }
+
+    #[test]
+    fn from_execute_query_error() {
+        let err: Error = ExecuteQueryError::Postgres(postgres_error()).into();
+
+        assert!(matches!(err, Error::ExecuteQuery(_)));
+        assert!(err.to_string().starts_with("Failed to execute query: "));
+    }
+
+    #[test]
+    fn from_json_error() {
+        let err: Error = serde_json::from_str::<u32>("{").unwrap_err().into();
+
+        assert!(matches!(err, Error::ParseConfig(_)));
+        assert!(err.to_string().starts_with("Failed to parse JSON: "));
+    }
+
+    #[test]
+    fn from_template_error() {
+        let context = tera::Context::new();
+        let source = tera::Tera::one_off("{{ broken", &context, false).unwrap_err();
+        let err: Error = ("message", source).into();
+
+        assert!(matches!(err, Error::RenderTemplate("message", _)));
+        assert!(err.to_string().starts_with("Failed to render message: "));
+    }
+
+    #[test]
+    fn from_unknown_key() {
+        let err: Error = "ColumnLimitMissed".to_string().into();
+
+        assert!(matches!(err, Error::UnknownInspector(_)));
+        assert_eq!(err.to_string(), "Unknown inspector: ColumnLimitMissed");
+    }
+
+    #[test]
+    fn from_parse_row_error() {
+        let err: Error = ParseRowError::Postgres(postgres_error()).into();
+
+        assert!(matches!(err, Error::ParseRow(_)));
+        assert!(err.to_string().starts_with("Failed to parse row: "));
+    }
+
+    #[test]
+    fn source_chain() {
+        let io = || IoError::other("broken");
+        let context = tera::Context::new();
+        let errors: Vec<Error> = vec![
+            EstablishConnectionError::Postgres(postgres_error()).into(),
+            ExecuteQueryError::Postgres(postgres_error()).into(),
+            ParseRowError::Postgres(postgres_error()).into(),
+            Error::PingDatabase(ExecuteQueryError::Postgres(postgres_error())),
+            serde_json::from_str::<u32>("{").unwrap_err().into(),
+            Error::SerializeJson(serde_json::from_str::<u32>("{").unwrap_err()),
+            toml::from_str::<u32>("=").unwrap_err().into(),
+            serde_yaml::from_str::<u32>("[").unwrap_err().into(),
+            Error::ReadConfig(io()),
+            Error::ReadFile(PathBuf::from("lint.yml"), io()),
+            Error::WriteFile(PathBuf::from("lint.yml"), io()),
+            ToSqlError::Name("none".into()).into(),
+            (
+                "query",
+                tera::Tera::one_off("{{", &context, false).unwrap_err(),
+            )
+                .into(),
+        ];
+
+        for err in errors {
+            assert!(err.source().is_some(), "no source of {:?}", err);
+        }
+        assert!(Error::UnknownInspector("foo".into()).source().is_none());
     }
 }
@@ -5,6 +5,7 @@ use tera::Context;
 
 use crate::client::{Client, TryFromRow};
 use crate::error::{Error, Result};
+use crate::problem::Templates;
 use crate::to_sql::ToSql;
 use crate::{CustomProblem, Problem};
 
@@ -13,11 +14,21 @@ pub trait Inspector: Sized {
     type Problem: Problem;
 
     fn build(key: &str, value: &str) -> Result<Self>;
+    /// The key of the rule in the config if the inspector looks for problems
+    /// of a single kind (used by `#[linter]` to dispatch the config to inspectors).
+    fn key() -> Option<&'static str> {
+        None
+    }
     fn query(&self) -> Result<String>;
     fn parse(
         &self,
         row: <<Self::Problem as Problem>::Client as Client>::Row,
     ) -> Result<Self::Problem>;
+    /// Whether the inspector should run against the client with the given name.
+    /// Inspectors of several problems should check the problem they look for.
+    fn applies_to_client(&self, client: &'static str) -> bool {
+        Self::Problem::applies_to_client(client)
+    }
 }
 
 /// The implementation of an inspector based on a query template,
@@ -30,6 +41,13 @@ where
     type Problem: CustomProblem;
 
     fn query_() -> &'static str;
+    /// Raw templates of the problem along with the query of the inspector.
+    fn templates() -> Templates {
+        Templates {
+            query: Some(Self::query_()),
+            ..<Self::Problem as CustomProblem>::templates()
+        }
+    }
     fn __query(&self) -> Result<String> {
         let compact = Regex::new(r"\s\n+").unwrap();
         let strip = Regex::new(r"^ | *(;.*)?$").unwrap();
@@ -58,6 +76,9 @@ where
     fn build(_key: &str, value: &str) -> Result<Self> {
         serde_json::from_str(value).map_err(Error::ParseConfig)
     }
+    fn key() -> Option<&'static str> {
+        Some(<Self::Problem as CustomProblem>::kind_())
+    }
     fn query(&self) -> Result<String> {
         Ok(format!("{}{};", self.__query()?, self.to_sql()?))
     }
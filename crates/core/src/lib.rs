@@ -4,16 +4,20 @@ mod inspector;
 mod linter;
 mod problem;
 mod report;
+mod severity;
 mod to_sql;
 
-pub use client::Client;
 #[cfg(feature = "postgres")]
 pub use client::PostgresClient;
+pub use client::{AsyncClient, Client, Pool};
 pub use error::Result;
 pub use inspector::{CustomInspector, Inspector};
-pub use linter::Linter;
+pub use linter::{ConfigFormat, InvalidConfig, Linter, QueryCache};
 #[cfg(feature = "postgres")]
 pub use postgres_from_row::FromRow;
-pub use problem::{CustomProblem, Problem};
+pub use problem::{CustomProblem, Displayable, Problem, ProblemOrd, Rendered, Templates};
+pub use report::{merge_messages, Report, Summary};
 pub use serde::{Deserialize, Serialize};
-pub use tera::Context;
+pub use serde_json::Value;
+pub use severity::Severity;
+pub use tera::{Context, Tera};
@@ -1,11 +1,23 @@
-use crate::client::Client;
-use crate::error::Result;
+use crate::client::{AsyncClient, Client, Pool};
+use crate::error::{Error, Result};
 use crate::inspector::Inspector;
 use crate::problem::Problem;
 use crate::report::Report;
 
 use serde_json::value::RawValue;
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::future::Future;
+use std::io::Read;
+use std::panic::resume_unwind;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+type ClientOf<I> = <<I as Inspector>::Problem as Problem>::Client;
+type RowOf<I> = <ClientOf<I> as Client>::Row;
 
 /// Linter is a thin wrapper around the Inspector that binds things together.
 ///
@@ -13,24 +25,943 @@ use std::collections::HashMap;
 /// the Inspector) is that the enum inspector is built by macro expansion,
 /// and we need some macro to provide it. To do this the `derive(Linter)`
 /// macro is used.
+///
+/// Inspectors are executed in the alphabetical order of their keys in the config
+/// (regardless of the config format), so the report is reproducible between runs.
 pub trait Linter {
     type Inspector: Inspector;
 
+    /// Run inspectors configured in JSON.
     fn run(
         config: &str,
-        client: &mut <<Self::Inspector as Inspector>::Problem as Problem>::Client,
+        client: &mut ClientOf<Self::Inspector>,
+    ) -> Result<Report<<Self::Inspector as Inspector>::Problem>> {
+        let data: BTreeMap<String, Box<RawValue>> = serde_json::from_str(config)?;
+        let rules = data.into_iter().map(|(key, val)| (key, val.to_string()));
+        run_rules::<Self::Inspector>(rules, client)
+    }
+
+    /// Run inspectors configured in the file, whose format (JSON, YAML or TOML)
+    /// is defined by its extension (see `ConfigFormat::from_path`).
+    fn run_file(
+        path: impl AsRef<Path>,
+        client: &mut ClientOf<Self::Inspector>,
+    ) -> Result<Report<<Self::Inspector as Inspector>::Problem>> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path)
+            .ok_or_else(|| Error::UnsupportedFormat(path.to_path_buf()))?;
+        let file = File::open(path).map_err(|err| Error::ReadFile(path.to_path_buf(), err))?;
+        Self::run_reader(file, format, client)
+    }
+
+    /// Run inspectors configured in the given format by any reader (like a file or stdin).
+    fn run_reader(
+        mut reader: impl Read,
+        format: ConfigFormat,
+        client: &mut ClientOf<Self::Inspector>,
+    ) -> Result<Report<<Self::Inspector as Inspector>::Problem>> {
+        let mut config = String::new();
+        reader
+            .read_to_string(&mut config)
+            .map_err(Error::ReadConfig)?;
+        match format {
+            ConfigFormat::Json => Self::run(&config, client),
+            ConfigFormat::Yaml => Self::run_yaml(&config, client),
+            ConfigFormat::Toml => Self::run_toml(&config, client),
+        }
+    }
+
+    /// Run inspectors configured in JSON with the asynchronous client,
+    /// which produces the same rows as the synchronous client of the problems.
+    /// Inspectors are executed sequentially, one query at a time.
+    fn run_async<'a, C>(
+        config: &'a str,
+        client: &'a mut C,
+    ) -> impl Future<Output = Result<Report<<Self::Inspector as Inspector>::Problem>>> + 'a
+    where
+        C: AsyncClient<Row = RowOf<Self::Inspector>>,
+    {
+        async move {
+            let data: BTreeMap<String, Box<RawValue>> = serde_json::from_str(config)?;
+            let mut report = Report::default();
+            for (key, val) in data {
+                // The async client cannot be called from the hook of `run_rule_with`
+                let Some(inspector) = build_rule::<Self::Inspector>(&key, &val.to_string())? else {
+                    continue;
+                };
+                for row in client.query(&inspector.query()?).await? {
+                    report.insert(inspector.parse(row)?);
+                }
+            }
+            Ok(report)
+        }
+    }
+
+    /// Run inspectors configured in JSON taking their queries from the cache
+    /// (queries which are absent in the cache are rendered and stored there).
+    fn run_cached(
+        config: &str,
+        client: &mut ClientOf<Self::Inspector>,
+        cache: &mut QueryCache,
+    ) -> Result<Report<<Self::Inspector as Inspector>::Problem>> {
+        let data: BTreeMap<String, Box<RawValue>> = serde_json::from_str(config)?;
+        let mut report = Report::default();
+        for (key, val) in data {
+            let val = val.to_string();
+            let problems = run_rule_with::<Self::Inspector>(&key, &val, |inspector| {
+                let query = match cache.queries.entry((key.clone(), val.clone())) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(inspector.query()?),
+                };
+                Ok(client.query(query)?)
+            })?;
+            report.extend(problems);
+        }
+        Ok(report)
+    }
+
+    /// Run inspectors configured in JSON after checking that the database
+    /// is reachable (before any inspector is built).
+    fn run_checked(
+        config: &str,
+        client: &mut ClientOf<Self::Inspector>,
+    ) -> Result<Report<<Self::Inspector as Inspector>::Problem>> {
+        client.ping().map_err(Error::PingDatabase)?;
+        Self::run(config, client)
+    }
+
+    /// Run inspectors configured in YAML (the format of the config template
+    /// generated by the `#[problem]` macro).
+    fn run_yaml(
+        config: &str,
+        client: &mut ClientOf<Self::Inspector>,
+    ) -> Result<Report<<Self::Inspector as Inspector>::Problem>> {
+        let data: BTreeMap<String, Value> = serde_yaml::from_str(config)?;
+        let rules = data.into_iter().map(|(key, val)| (key, val.to_string()));
+        run_rules::<Self::Inspector>(rules, client)
+    }
+
+    /// Run inspectors configured in several YAML (or JSON) documents.
+    /// Configs are merged by keys, so that a later config overrides
+    /// the whole config of the same inspector from earlier ones.
+    fn run_many(
+        configs: &[&str],
+        client: &mut ClientOf<Self::Inspector>,
+    ) -> Result<Report<<Self::Inspector as Inspector>::Problem>> {
+        let mut data: BTreeMap<String, Value> = BTreeMap::new();
+        for config in configs {
+            data.extend(serde_yaml::from_str::<BTreeMap<String, Value>>(config)?);
+        }
+        let rules = data.into_iter().map(|(key, val)| (key, val.to_string()));
+        run_rules::<Self::Inspector>(rules, client)
+    }
+
+    /// Run inspectors configured in TOML, where every top-level table
+    /// is the config of the inspector named by its key.
+    fn run_toml(
+        config: &str,
+        client: &mut ClientOf<Self::Inspector>,
+    ) -> Result<Report<<Self::Inspector as Inspector>::Problem>> {
+        let data: BTreeMap<String, Value> = toml::from_str(config)?;
+        let rules = data.into_iter().map(|(key, val)| (key, val.to_string()));
+        run_rules::<Self::Inspector>(rules, client)
+    }
+
+    /// Run inspectors configured in JSON calling the `callback` with the key
+    /// of every inspector and the number of rows it matched after it completes.
+    fn run_with_progress(
+        config: &str,
+        client: &mut ClientOf<Self::Inspector>,
+        mut callback: impl FnMut(&str, usize),
     ) -> Result<Report<<Self::Inspector as Inspector>::Problem>> {
+        let data: BTreeMap<String, Box<RawValue>> = serde_json::from_str(config)?;
+        let mut report = Report::default();
+        for (key, val) in data {
+            let problems = run_rule::<Self::Inspector>(&key, &val.to_string(), client)?;
+            callback(&key, problems.len());
+            report.extend(problems);
+        }
+        Ok(report)
+    }
+
+    /// Run inspectors configured in JSON measuring how long every one of them
+    /// took to execute its query and parse the rows.
+    #[allow(clippy::type_complexity)]
+    fn run_timed(
+        config: &str,
+        client: &mut ClientOf<Self::Inspector>,
+    ) -> Result<(
+        Report<<Self::Inspector as Inspector>::Problem>,
+        BTreeMap<String, Duration>,
+    )> {
+        let data: BTreeMap<String, Box<RawValue>> = serde_json::from_str(config)?;
         let mut report = Report::default();
-        let data: HashMap<String, Box<RawValue>> = serde_json::from_str(config)?;
+        let mut timings = BTreeMap::new();
         for (key, val) in data {
-            let inspector = Self::Inspector::build(&key, &val.to_string())?;
-            let query = inspector.query()?;
-            let rows = client.query(&query)?;
-            for row in rows {
-                let problem = inspector.parse(row)?;
-                report.insert(problem);
+            let mut start = None;
+            let problems = run_rule_with::<Self::Inspector>(&key, &val.to_string(), |inspector| {
+                let query = inspector.query()?;
+                start = Some(Instant::now());
+                Ok(client.query(&query)?)
+            })?;
+            report.extend(problems);
+            // Inspectors skipped for the client are not timed
+            if let Some(start) = start {
+                timings.insert(key, start.elapsed());
+            }
+        }
+        Ok((report, timings))
+    }
+
+    /// Check that every inspector configured in JSON can be built,
+    /// and return keys unknown to the linter separately from errors
+    /// of the misconfigured inspectors (or the error of the config itself).
+    fn validate(config: &str) -> std::result::Result<(), InvalidConfig> {
+        let data: BTreeMap<String, Box<RawValue>> =
+            serde_json::from_str(config).map_err(|err| InvalidConfig::Parse(err.into()))?;
+        let mut unknown = vec![];
+        let mut invalid = BTreeMap::new();
+        for (key, val) in data {
+            match Self::Inspector::build(&key, &val.to_string()) {
+                Ok(_) => {}
+                Err(Error::UnknownInspector(_)) => unknown.push(key),
+                Err(err) => {
+                    invalid.insert(key, err);
+                }
+            }
+        }
+        if unknown.is_empty() && invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidConfig::Rules { unknown, invalid })
+        }
+    }
+
+    /// Render queries of inspectors configured in JSON keyed by their names
+    /// without executing them (no connection is needed).
+    fn dry_run(config: &str) -> Result<BTreeMap<String, String>> {
+        let data: BTreeMap<String, Box<RawValue>> = serde_json::from_str(config)?;
+        let mut queries = BTreeMap::new();
+        for (key, val) in data {
+            let query = Self::Inspector::build(&key, &val.to_string())?.query()?;
+            queries.insert(key, query);
+        }
+        Ok(queries)
+    }
+
+    /// Run inspectors configured in JSON collecting at most `max` problems in total
+    /// (the limit is global, not per rule). As soon as the limit is exceeded,
+    /// the remaining rows and rules are skipped, and the report is marked as truncated.
+    ///
+    /// The limit is pushed into the database by wrapping every query
+    /// into `SELECT * FROM (<query>) q LIMIT <remaining + 1>`, so the queries
+    /// should be single `SELECT` statements (a trailing `;` is allowed).
+    fn run_with_limit(
+        config: &str,
+        client: &mut ClientOf<Self::Inspector>,
+        max: usize,
+    ) -> Result<Report<<Self::Inspector as Inspector>::Problem>> {
+        let data: BTreeMap<String, Box<RawValue>> = serde_json::from_str(config)?;
+        let mut report = Report::default();
+        for (key, val) in data {
+            let remaining = max.saturating_sub(report.count());
+            let mut truncated = false;
+            let problems = run_rule_with::<Self::Inspector>(&key, &val.to_string(), |inspector| {
+                let mut rows = client.query(&limit_query(&inspector.query()?, remaining + 1))?;
+                truncated = rows.len() > remaining;
+                rows.truncate(remaining);
+                Ok(rows)
+            })?;
+            report.extend(problems);
+            if truncated {
+                report.truncate();
+                return Ok(report);
             }
         }
         Ok(report)
     }
+
+    /// Run inspectors configured in JSON ignoring keys unknown to the linter
+    /// (other errors, including invalid configs of known inspectors, still abort the run).
+    /// Along with the report it returns the skipped keys.
+    #[allow(clippy::type_complexity)]
+    fn run_skipping_unknown(
+        config: &str,
+        client: &mut ClientOf<Self::Inspector>,
+    ) -> Result<(Report<<Self::Inspector as Inspector>::Problem>, Vec<String>)> {
+        let data: BTreeMap<String, Box<RawValue>> = serde_json::from_str(config)?;
+        let mut report = Report::default();
+        let mut skipped = vec![];
+        for (key, val) in data {
+            match run_rule::<Self::Inspector>(&key, &val.to_string(), client) {
+                Ok(problems) => report.extend(problems),
+                Err(Error::UnknownInspector(_)) => skipped.push(key),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok((report, skipped))
+    }
+
+    /// Run only the inspector configured in JSON under the `key`.
+    /// Fails if the key is absent in the config.
+    fn run_one(
+        key: &str,
+        config: &str,
+        client: &mut ClientOf<Self::Inspector>,
+    ) -> Result<Report<<Self::Inspector as Inspector>::Problem>> {
+        let mut data: BTreeMap<String, Box<RawValue>> = serde_json::from_str(config)?;
+        let val = data
+            .remove(key)
+            .ok_or_else(|| Error::MissingRule(key.to_string()))?;
+        let problems = run_rule::<Self::Inspector>(key, &val.to_string(), client)?;
+        Ok(Report::from_iter(problems))
+    }
+
+    /// Run inspectors configured in JSON without stopping at the first failure.
+    /// Along with the problems found by successful inspectors it returns errors
+    /// of the failed ones keyed by their names in the config.
+    ///
+    /// Only the failure to parse the config itself aborts the run.
+    #[allow(clippy::type_complexity)]
+    fn run_lenient(
+        config: &str,
+        client: &mut ClientOf<Self::Inspector>,
+    ) -> Result<(
+        Report<<Self::Inspector as Inspector>::Problem>,
+        Vec<(String, Error)>,
+    )> {
+        let data: BTreeMap<String, Box<RawValue>> = serde_json::from_str(config)?;
+        let mut report = Report::default();
+        let mut errors = vec![];
+        for (key, val) in data {
+            match run_rule::<Self::Inspector>(&key, &val.to_string(), client) {
+                Ok(problems) => report.extend(problems),
+                Err(err) => errors.push((key, err)),
+            }
+        }
+        Ok((report, errors))
+    }
+
+    /// Run inspectors configured in JSON distributing them across `threads` workers.
+    /// Every worker takes its own client from the pool.
+    ///
+    /// The order in which workers find problems is non-deterministic,
+    /// that's why the merged report is compacted (sorted by fingerprints without duplicates).
+    fn run_parallel<P>(
+        config: &str,
+        pool: &P,
+        threads: usize,
+    ) -> Result<Report<<Self::Inspector as Inspector>::Problem>>
+    where
+        P: Pool<Client = ClientOf<Self::Inspector>>,
+        <Self::Inspector as Inspector>::Problem: Send,
+    {
+        let data: BTreeMap<String, Box<RawValue>> = serde_json::from_str(config)?;
+        let threads = threads.max(1);
+        let mut chunks: Vec<Vec<(String, String)>> = (0..threads).map(|_| vec![]).collect();
+        for (index, (key, val)) in data.into_iter().enumerate() {
+            chunks[index % threads].push((key, val.to_string()));
+        }
+
+        let reports: Vec<Result<_>> = thread::scope(|scope| {
+            let workers: Vec<_> = chunks
+                .into_iter()
+                .filter(|rules| !rules.is_empty())
+                .map(|rules| {
+                    scope.spawn(move || {
+                        let mut client = pool.get()?;
+                        run_rules::<Self::Inspector>(rules, &mut client)
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap_or_else(|err| resume_unwind(err)))
+                .collect()
+        });
+
+        let mut report = Report::default();
+        for part in reports {
+            report.extend(part?);
+        }
+        report.compact()
+    }
+}
+
+/// Errors of the linter config found by `Linter::validate`
+#[derive(Debug)]
+pub enum InvalidConfig {
+    /// The config cannot be parsed
+    Parse(Error),
+    /// Keys unknown to the linter, and errors of the misconfigured inspectors by their keys
+    Rules {
+        unknown: Vec<String>,
+        invalid: BTreeMap<String, Error>,
+    },
+}
+
+/// The format of the linter config
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConfigFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// The format defined by the extension of the file
+    /// (`.json`, `.yaml`/`.yml` or `.toml`).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Some(Self::Json),
+            Some("yaml" | "yml") => Some(Self::Yaml),
+            Some("toml") => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Queries rendered by inspectors keyed by their names and configs.
+///
+/// The cache is opt-in (see `Linter::run_cached`) and is useful when the same
+/// config runs many times (like in a watch mode), where it saves the rendering
+/// of query templates and their WHERE clauses.
+#[derive(Clone, Debug, Default)]
+pub struct QueryCache {
+    queries: HashMap<(String, String), String>,
+}
+
+impl QueryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.queries.clear();
+    }
+}
+
+// Build inspectors from (key, JSON config) pairs and collect the problems they find
+fn run_rules<I: Inspector>(
+    rules: impl IntoIterator<Item = (String, String)>,
+    client: &mut ClientOf<I>,
+) -> Result<Report<I::Problem>> {
+    let mut report = Report::default();
+    for (key, val) in rules {
+        report.extend(run_rule::<I>(&key, &val, client)?);
+    }
+    Ok(report)
+}
+
+// Build the inspector from its key and JSON config and collect the problems it finds
+fn run_rule<I: Inspector>(
+    key: &str,
+    val: &str,
+    client: &mut ClientOf<I>,
+) -> Result<Vec<I::Problem>> {
+    run_rule_with::<I>(key, val, |inspector| Ok(client.query(&inspector.query()?)?))
+}
+
+// The same as `run_rule`, but the rows are fetched by the hook
+// (to take the query from the cache, to measure the time, or to limit the rows)
+fn run_rule_with<I: Inspector>(
+    key: &str,
+    val: &str,
+    fetch: impl FnOnce(&I) -> Result<Vec<RowOf<I>>>,
+) -> Result<Vec<I::Problem>> {
+    match build_rule::<I>(key, val)? {
+        Some(inspector) => fetch(&inspector)?
+            .into_iter()
+            .map(|row| inspector.parse(row))
+            .collect(),
+        None => Ok(vec![]),
+    }
+}
+
+// Wrap the query to fetch at most `limit` rows
+fn limit_query(query: &str, limit: usize) -> String {
+    let query = query.trim_end().trim_end_matches(';');
+    format!("SELECT * FROM ({}\n) q LIMIT {}", query, limit)
+}
+
+// Build the inspector from its key and JSON config unless it skips the client
+fn build_rule<I: Inspector>(key: &str, val: &str) -> Result<Option<I>> {
+    let inspector = I::build(key, val)?;
+    Ok(applies(&inspector).then_some(inspector))
+}
+
+// Check whether the inspector should run against the client of its problems
+// (a client without a name runs every inspector)
+fn applies<I: Inspector>(inspector: &I) -> bool {
+    let name = <ClientOf<I> as Client>::NAME;
+    name.is_empty() || inspector.applies_to_client(name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::ExecuteQueryError;
+    use serde::Deserialize;
+    use std::fs;
+    use std::io::Cursor;
+    use std::task::{Context, Poll, Waker};
+
+    // The client returning names of the tables starting with the query
+    // (and recording the queries it received)
+    struct Catalog {
+        tables: Vec<&'static str>,
+        queries: Vec<String>,
+    }
+    impl Client for Catalog {
+        type Row = String;
+        const NAME: &'static str = "Catalog";
+
+        fn query(&mut self, query: &str) -> std::result::Result<Vec<String>, ExecuteQueryError> {
+            self.queries.push(query.to_string());
+            let (query, limit) = query
+                .strip_prefix("SELECT * FROM (")
+                .and_then(|q| q.rsplit_once("\n) q LIMIT "))
+                .map_or((query, usize::MAX), |(q, n)| (q, n.parse().unwrap()));
+            Ok(self
+                .tables
+                .iter()
+                .filter(|t| t.starts_with(query))
+                .take(limit)
+                .map(|t| t.to_string())
+                .collect())
+        }
+    }
+
+    // The table found by the rule
+    #[derive(Debug, PartialEq)]
+    struct Found {
+        kind: &'static str,
+        table: String,
+    }
+    impl Problem for Found {
+        type Client = Catalog;
+
+        fn kind(&self) -> &'static str {
+            self.kind
+        }
+        fn message(&self) -> Result<String> {
+            Ok(format!("{} {}", self.kind, self.table))
+        }
+        fn migration(&self) -> Option<Result<String>> {
+            None
+        }
+        fn rollback(&self) -> Option<Result<String>> {
+            None
+        }
+    }
+
+    // The rule looking for tables by prefix ("broken" rule fails to build its query,
+    // and "remote" rule is only applicable to another client)
+    #[derive(Deserialize)]
+    struct Config {
+        prefix: String,
+    }
+    struct Rule {
+        kind: &'static str,
+        prefix: String,
+    }
+    impl Inspector for Rule {
+        type Problem = Found;
+
+        fn build(key: &str, value: &str) -> Result<Self> {
+            let kind = match key {
+                "broken" => "broken",
+                "remote" => "remote",
+                "temporary" => "temporary",
+                "users" => "users",
+                _ => return Err(key.to_string().into()),
+            };
+            let config: Config = serde_json::from_str(value)?;
+            Ok(Self {
+                kind,
+                prefix: config.prefix,
+            })
+        }
+        fn query(&self) -> Result<String> {
+            match self.kind {
+                "broken" => tera::Tera::one_off("{{ missed }}", &tera::Context::new(), false)
+                    .map_err(|e| ("query", e).into()),
+                _ => Ok(self.prefix.clone()),
+            }
+        }
+        fn parse(&self, row: String) -> Result<Found> {
+            Ok(Found {
+                kind: self.kind,
+                table: row,
+            })
+        }
+        fn applies_to_client(&self, client: &'static str) -> bool {
+            match self.kind {
+                "remote" => client == "RemoteCatalog",
+                _ => Found::applies_to_client(client),
+            }
+        }
+    }
+
+    struct TestLinter;
+    impl Linter for TestLinter {
+        type Inspector = Rule;
+    }
+
+    fn catalog() -> Catalog {
+        Catalog {
+            tables: vec!["tmp_foo", "users", "tmp_bar", "user_roles"],
+            queries: vec![],
+        }
+    }
+
+    #[test]
+    fn run() {
+        let report = TestLinter::run(r#"{"temporary": {"prefix": "tmp_"}}"#, &mut catalog());
+
+        assert_eq!(
+            report.unwrap().message().unwrap(),
+            "temporary tmp_foo\ntemporary tmp_bar"
+        );
+    }
+
+    #[test]
+    fn run_in_alphabetical_order() {
+        let config = r#"{"users": {"prefix": "user"}, "temporary": {"prefix": "tmp_"}}"#;
+        let report = TestLinter::run(config, &mut catalog());
+
+        assert_eq!(
+            report.unwrap().message().unwrap(),
+            "temporary tmp_foo\ntemporary tmp_bar\nusers users\nusers user_roles"
+        );
+    }
+
+    // The asynchronous client returning names of the tables starting with the query
+    struct AsyncCatalog(Catalog);
+    impl AsyncClient for AsyncCatalog {
+        type Row = String;
+
+        fn query(
+            &mut self,
+            query: &str,
+        ) -> impl Future<Output = std::result::Result<Vec<String>, ExecuteQueryError>> {
+            std::future::ready(self.0.query(query))
+        }
+    }
+
+    #[test]
+    fn run_async() {
+        let mut client = AsyncCatalog(catalog());
+        let mut future = Box::pin(TestLinter::run_async(
+            r#"{"users": {"prefix": "user"}}"#,
+            &mut client,
+        ));
+        let mut context = Context::from_waker(Waker::noop());
+        let Poll::Ready(report) = future.as_mut().poll(&mut context) else {
+            panic!("the future is expected to be ready");
+        };
+
+        assert_eq!(
+            report.unwrap().message().unwrap(),
+            "users users\nusers user_roles"
+        );
+    }
+
+    #[test]
+    fn run_cached() {
+        let config = r#"{"temporary": {"prefix": "tmp_"}, "users": {"prefix": "user"}}"#;
+        let mut cache = QueryCache::new();
+        TestLinter::run_cached(config, &mut catalog(), &mut cache).unwrap();
+        let report = TestLinter::run_cached(config, &mut catalog(), &mut cache);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(report.unwrap().count(), 4);
+    }
+
+    #[test]
+    fn run_checked() {
+        let report = TestLinter::run_checked(r#"{"users": {"prefix": "user"}}"#, &mut catalog());
+
+        assert_eq!(
+            report.unwrap().message().unwrap(),
+            "users users\nusers user_roles"
+        );
+    }
+
+    #[test]
+    fn run_skipping_another_client() {
+        let config = r#"{"remote": {"prefix": "tmp_"}, "users": {"prefix": "user"}}"#;
+        let report = TestLinter::run(config, &mut catalog());
+
+        assert!(Found::applies_to_client("Catalog"));
+        assert_eq!(
+            report.unwrap().message().unwrap(),
+            "users users\nusers user_roles"
+        );
+    }
+
+    #[test]
+    fn run_yaml() {
+        let config = "temporary:\n  prefix: tmp_\n";
+        let report = TestLinter::run_yaml(config, &mut catalog());
+
+        assert_eq!(
+            report.unwrap().message().unwrap(),
+            "temporary tmp_foo\ntemporary tmp_bar"
+        );
+    }
+
+    #[test]
+    fn run_yaml_with_invalid_config() {
+        let report = TestLinter::run_yaml("temporary: [", &mut catalog());
+
+        assert!(matches!(report, Err(Error::ParseYaml(_))));
+    }
+
+    #[test]
+    fn run_many() {
+        let shared = "temporary:\n  prefix: tmp_\nusers:\n  prefix: user\n";
+        let team = "users:\n  prefix: user_\n";
+        let report = TestLinter::run_many(&[shared, team], &mut catalog());
+
+        assert_eq!(
+            report.unwrap().message().unwrap(),
+            "temporary tmp_foo\ntemporary tmp_bar\nusers user_roles"
+        );
+    }
+
+    #[test]
+    fn run_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.yml");
+        fs::write(&path, "users:\n  prefix: user\n").unwrap();
+        let report = TestLinter::run_file(&path, &mut catalog());
+
+        assert_eq!(
+            report.unwrap().message().unwrap(),
+            "users users\nusers user_roles"
+        );
+    }
+
+    #[test]
+    fn run_file_with_unsupported_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.ini");
+        fs::write(&path, "[users]\nprefix = user\n").unwrap();
+        let report = TestLinter::run_file(&path, &mut catalog());
+
+        assert!(matches!(report, Err(Error::UnsupportedFormat(p)) if p == path));
+    }
+
+    #[test]
+    fn run_reader() {
+        let config = Cursor::new("[users]\nprefix = \"user\"\n".as_bytes());
+        let report = TestLinter::run_reader(config, ConfigFormat::Toml, &mut catalog());
+
+        assert_eq!(
+            report.unwrap().message().unwrap(),
+            "users users\nusers user_roles"
+        );
+    }
+
+    #[test]
+    fn run_toml() {
+        let config = "[temporary]\nprefix = \"tmp_\"\n";
+        let report = TestLinter::run_toml(config, &mut catalog());
+
+        assert_eq!(
+            report.unwrap().message().unwrap(),
+            "temporary tmp_foo\ntemporary tmp_bar"
+        );
+    }
+
+    #[test]
+    fn run_toml_with_invalid_config() {
+        let report = TestLinter::run_toml("[temporary", &mut catalog());
+
+        assert!(matches!(report, Err(Error::ParseToml(_))));
+    }
+
+    #[test]
+    fn run_parallel() {
+        let config = r#"{"temporary": {"prefix": "tmp_"}, "users": {"prefix": "user"}}"#;
+        let pool = || Ok(catalog());
+        let report = TestLinter::run_parallel(config, &pool, 2);
+
+        assert_eq!(
+            report.unwrap().message().unwrap(),
+            "temporary tmp_bar\ntemporary tmp_foo\nusers user_roles\nusers users"
+        );
+    }
+
+    #[test]
+    fn run_with_progress() {
+        let config = r#"{"temporary": {"prefix": "tmp_foo"}, "users": {"prefix": "user"}}"#;
+        let mut progress = vec![];
+        let report = TestLinter::run_with_progress(config, &mut catalog(), |key, matched| {
+            progress.push((key.to_string(), matched))
+        });
+
+        assert_eq!(report.unwrap().count(), 3);
+        assert_eq!(
+            progress,
+            vec![("temporary".to_string(), 1), ("users".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn run_timed() {
+        let config = r#"{"temporary": {"prefix": "tmp_"}, "users": {"prefix": "user"}}"#;
+        let (report, timings) = TestLinter::run_timed(config, &mut catalog()).unwrap();
+
+        assert_eq!(report.count(), 4);
+        assert_eq!(
+            timings.keys().collect::<Vec<_>>(),
+            vec!["temporary", "users"]
+        );
+    }
+
+    #[test]
+    fn validate() {
+        let config = r#"{"temporary": {"prefix": "tmp_"}, "users": {"prefix": "user"}}"#;
+
+        assert!(TestLinter::validate(config).is_ok());
+    }
+
+    #[test]
+    fn validate_with_invalid_keys() {
+        let config = r#"{"tables": {}, "temporary": {"prefix": "tmp_"}, "users": {}, "views": {}}"#;
+        let Err(InvalidConfig::Rules { unknown, invalid }) = TestLinter::validate(config) else {
+            panic!("invalid rules are expected");
+        };
+
+        assert_eq!(unknown, vec!["tables", "views"]);
+        assert_eq!(invalid.keys().collect::<Vec<_>>(), vec!["users"]);
+        assert!(matches!(invalid["users"], Error::ParseConfig(_)));
+    }
+
+    #[test]
+    fn validate_unparseable_config() {
+        let error = TestLinter::validate("{").unwrap_err();
+
+        assert!(matches!(error, InvalidConfig::Parse(Error::ParseConfig(_))));
+    }
+
+    #[test]
+    fn dry_run() {
+        let config = r#"{"temporary": {"prefix": "tmp_"}, "users": {"prefix": "user"}}"#;
+        let queries = TestLinter::dry_run(config).unwrap();
+
+        assert_eq!(queries.len(), 2);
+        assert_eq!(queries["temporary"], "tmp_");
+        assert_eq!(queries["users"], "user");
+    }
+
+    #[test]
+    fn run_with_limit() {
+        let config = r#"{"temporary": {"prefix": "tmp_"}, "users": {"prefix": "user"}}"#;
+        let report = TestLinter::run_with_limit(config, &mut catalog(), 3).unwrap();
+
+        assert!(report.is_truncated());
+        assert_eq!(
+            report.message().unwrap(),
+            "temporary tmp_foo\ntemporary tmp_bar\nusers users"
+        );
+    }
+
+    #[test]
+    fn run_with_limit_in_query() {
+        let config = r#"{"temporary": {"prefix": "tmp_"}, "users": {"prefix": "user"}}"#;
+        let mut client = catalog();
+        TestLinter::run_with_limit(config, &mut client, 3).unwrap();
+
+        assert_eq!(
+            client.queries,
+            [
+                "SELECT * FROM (tmp_\n) q LIMIT 4",
+                "SELECT * FROM (user\n) q LIMIT 2"
+            ]
+        );
+    }
+
+    #[test]
+    fn limit_query_without_semicolon() {
+        assert_eq!(
+            limit_query("SELECT 1;\n", 5),
+            "SELECT * FROM (SELECT 1\n) q LIMIT 5"
+        );
+    }
+
+    #[test]
+    fn run_within_limit() {
+        let config = r#"{"temporary": {"prefix": "tmp_"}, "users": {"prefix": "user"}}"#;
+        let report = TestLinter::run_with_limit(config, &mut catalog(), 4).unwrap();
+
+        assert!(!report.is_truncated());
+        assert_eq!(report.count(), 4);
+    }
+
+    #[test]
+    fn run_skipping_unknown() {
+        let config = r#"{"tables": {}, "users": {"prefix": "user"}, "views": {}}"#;
+        let (report, skipped) = TestLinter::run_skipping_unknown(config, &mut catalog()).unwrap();
+
+        assert_eq!(report.message().unwrap(), "users users\nusers user_roles");
+        assert_eq!(skipped, vec!["tables", "views"]);
+    }
+
+    #[test]
+    fn run_one() {
+        let config = r#"{"broken": {"prefix": "tmp_"}, "users": {"prefix": "user"}}"#;
+        let report = TestLinter::run_one("users", config, &mut catalog());
+
+        assert_eq!(
+            report.unwrap().message().unwrap(),
+            "users users\nusers user_roles"
+        );
+    }
+
+    #[test]
+    fn run_one_missing() {
+        let config = r#"{"users": {"prefix": "user"}}"#;
+        let report = TestLinter::run_one("temporary", config, &mut catalog());
+
+        assert!(matches!(report, Err(Error::MissingRule(key)) if key == "temporary"));
+    }
+
+    #[test]
+    fn run_lenient() {
+        let config = r#"{"broken": {"prefix": "tmp_"}, "temporary": {"prefix": "tmp_"}}"#;
+        let (report, errors) = TestLinter::run_lenient(config, &mut catalog()).unwrap();
+
+        assert_eq!(
+            report.message().unwrap(),
+            "temporary tmp_foo\ntemporary tmp_bar"
+        );
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, "broken");
+        assert!(matches!(errors[0].1, Error::RenderTemplate("query", _)));
+    }
+
+    #[test]
+    fn run_with_unknown_rule() {
+        let report = TestLinter::run_yaml("unknown:\n  prefix: tmp_\n", &mut catalog());
+
+        assert!(matches!(report, Err(Error::UnknownInspector(key)) if key == "unknown"));
+    }
+
+    #[test]
+    fn run_with_malformed_config() {
+        let report = TestLinter::run(r#"{"temporary": "#, &mut catalog());
+
+        assert!(matches!(report, Err(Error::ParseConfig(_))));
+    }
 }
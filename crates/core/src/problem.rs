@@ -1,7 +1,15 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::any::type_name;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
 use tera::{Context, Tera};
 
 use crate::client::{Client, TryFromRow};
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::severity::Severity;
 
 /// A problem in the database that is reportable in the form of message and optional fixes.
 /// Its interface uses `Result<String>` to support templates whose rendering may fail.
@@ -12,10 +20,42 @@ pub trait Problem: Sized {
     fn kind(&self) -> &'static str;
     /// The message, describing the problem.
     fn message(&self) -> Result<String>;
+    /// The message in the given locale (falls back to the default message).
+    fn message_in(&self, _locale: &str) -> Result<String> {
+        self.message()
+    }
     /// The migration to fix the problem.
     fn migration(&self) -> Option<Result<String>>;
     /// The rollback of the migration.
     fn rollback(&self) -> Option<Result<String>>;
+    /// Whether the problem can be looked for by the client with the given name.
+    fn applies_to_client(_client: &'static str) -> bool {
+        true
+    }
+    /// The severity of the problem.
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+    /// The category of the problem like "performance" or "safety" (empty by default).
+    fn category(&self) -> &'static str {
+        ""
+    }
+    /// Tags of the problem.
+    fn tags(&self) -> &'static [&'static str] {
+        &[]
+    }
+    /// The link to the documentation describing the problem and its fix.
+    fn docs_url(&self) -> Option<&'static str> {
+        None
+    }
+    /// The (schema, table) pair of the database object the problem belongs to (if known).
+    fn target(&self) -> Option<(String, String)> {
+        None
+    }
+    /// Kinds of problems whose migrations must be applied before the migration of this one.
+    fn depends_on(&self) -> Vec<String> {
+        vec![]
+    }
 
     /// A helper method to implement Ord and Eq for problems
     fn id(&self) -> Result<String> {
@@ -24,12 +64,130 @@ pub trait Problem: Sized {
             self.kind(),
             self.message()?,
             self.migration().unwrap_or(Ok("".into()))?,
-            self.migration().unwrap_or(Ok("".into()))?,
+            self.rollback().unwrap_or(Ok("".into()))?,
         ))
     }
+    /// The message, migration, and rollback rendered at once.
+    fn render_all(&self) -> Result<Rendered> {
+        Ok(Rendered {
+            message: self.message()?,
+            migration: self.migration().transpose()?,
+            rollback: self.rollback().transpose()?,
+        })
+    }
+    /// The migration with an optional rollback (if the problem has a migration).
+    fn fix(&self) -> Option<Result<(String, Option<String>)>> {
+        let migration = self.migration()?;
+        Some(migration.and_then(|m| Ok((m, self.rollback().transpose()?))))
+    }
+    /// The JSON object with the kind, severity, and rendered templates of the problem.
+    fn to_json_value(&self) -> Result<Value> {
+        let rendered = self.render_all()?;
+        Ok(json!({
+            "kind": self.kind(),
+            "message": rendered.message,
+            "migration": rendered.migration,
+            "rollback": rendered.rollback,
+            "severity": self.severity(),
+        }))
+    }
+    /// The wrapper to print the message of the problem via `Display`
+    /// (the kind is printed when the message cannot be rendered).
+    fn display(&self) -> Displayable<'_, Self> {
+        Displayable(self)
+    }
+    /// The identity of the problem used to remove duplicates from reports.
+    /// It equals `id` by default, but can be overridden to avoid rendering
+    /// of templates (e.g. to identify problems by their kind and object name).
+    fn fingerprint(&self) -> Result<String> {
+        self.id()
+    }
+}
+
+/// The message, migration, and rollback of a problem, returned by `Problem::render_all`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Rendered {
+    pub message: String,
+    pub migration: Option<String>,
+    pub rollback: Option<String>,
+}
+
+/// The problem ordered by its fingerprint, which is computed once on wrapping.
+/// It allows problems to be kept in ordered collections like `BTreeSet`.
+#[derive(Clone, Debug)]
+pub struct ProblemOrd<P: Problem> {
+    fingerprint: String,
+    problem: P,
+}
+
+impl<P: Problem> ProblemOrd<P> {
+    pub fn new(problem: P) -> Result<Self> {
+        Ok(Self {
+            fingerprint: problem.fingerprint()?,
+            problem,
+        })
+    }
+
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    pub fn problem(&self) -> &P {
+        &self.problem
+    }
+
+    pub fn into_inner(self) -> P {
+        self.problem
+    }
+}
+
+impl<P: Problem> PartialEq for ProblemOrd<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.fingerprint == other.fingerprint
+    }
+}
+
+impl<P: Problem> Eq for ProblemOrd<P> {}
+
+impl<P: Problem> PartialOrd for ProblemOrd<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: Problem> Ord for ProblemOrd<P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.fingerprint.cmp(&other.fingerprint)
+    }
+}
+
+/// Helper struct to print problems, returned by `Problem::display`.
+pub struct Displayable<'a, P: Problem>(&'a P);
+
+impl<P: Problem> Display for Displayable<'_, P> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self.0.message() {
+            Ok(message) => write!(f, "{}", message),
+            Err(_) => write!(f, "{}", self.0.kind()),
+        }
+    }
+}
+
+/// Raw (not rendered) templates of a custom problem and its inspector.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct Templates {
+    pub kind: &'static str,
+    pub message: &'static str,
+    pub migration: Option<&'static str>,
+    pub rollback: Option<&'static str>,
+    pub query: Option<&'static str>,
 }
 
 /// A specific problem has some structure bound to the rendered templates.
+///
+/// Only the kind and the message are required. Informational problems without
+/// a fix skip both the migration and the rollback, so that reports ignore them
+/// when building migrations.
 pub trait CustomProblem
 where
     Self: Sized,
@@ -43,6 +201,10 @@ where
     fn kind_() -> &'static str;
     /// The template for the message describing the problem
     fn message_() -> &'static str;
+    /// The (optional) template for the message in the given locale
+    fn message_locale(_locale: &str) -> Option<&'static str> {
+        None
+    }
     /// The (optional) template for the migration to fix the problem
     fn migration_() -> Option<&'static str> {
         None
@@ -51,15 +213,95 @@ where
     fn rollback_() -> Option<&'static str> {
         None
     }
+    /// The severity of all problems of the kind
+    fn severity_() -> Severity {
+        Severity::default()
+    }
+    /// Whether problems of the kind can be looked for by the client with the given name
+    /// (`#[problem]` compares it to the `client` of the definition).
+    fn applies_to_client_(_client: &'static str) -> bool {
+        true
+    }
+    /// Variables available to all templates of the problem in addition to its own fields
+    /// (like the database name or the time of the run). On conflict the fields of the problem win.
+    fn globals() -> Context {
+        Context::new()
+    }
+    /// Raw templates of the problem (without the query of its inspector).
+    fn templates() -> Templates {
+        Templates {
+            kind: Self::kind_(),
+            message: Self::message_(),
+            migration: Self::migration_(),
+            rollback: Self::rollback_(),
+            query: None,
+        }
+    }
+    /// The hook to register custom filters and functions used by the templates.
+    /// It is called once when the templates of the problem are parsed,
+    /// and the instance of Tera is cached for all problems of the same type.
+    fn register_filters(_tera: &mut Tera) {}
+    /// Parse the templates of the problem ahead of the first rendering
+    /// to find out broken templates early (for example, at startup).
+    fn warm_templates() -> Result<()> {
+        Self::__tera().map(|_| ())
+    }
     // Helper method, not a part of public interface
     #[doc(hidden)]
-    fn __render_template(&self, template: &'static str) -> Result<String> {
-        let mut tera = Tera::default();
+    fn __tera() -> Result<Arc<Tera>> {
+        Self::__tera_in(None)
+    }
+    // Helper method, not a part of public interface.
+    // The message template is taken for the locale if the problem provides it.
+    #[doc(hidden)]
+    fn __tera_in(locale: Option<&str>) -> Result<Arc<Tera>> {
+        // The static is shared by all implementations, hence the instances are keyed
+        // by the type (not the kind, which can be shared by several types).
+        // Renders only take the read lock, so they don't block each other.
+        type Cache = HashMap<(&'static str, Option<String>), Arc<Tera>>;
+        static CACHE: OnceLock<RwLock<Cache>> = OnceLock::new();
+
         let kind = Self::kind_();
-        let context = self.into();
-        tera.add_raw_template(kind, template)
-            .and_then(|_| tera.render(kind, &context))
-            .map_err(|err| (kind, err).into())
+        let key = (type_name::<Self>(), locale.map(String::from));
+        let cache = CACHE.get_or_init(Default::default);
+        if let Some(tera) = cache
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+        {
+            return Ok(tera.clone());
+        }
+
+        let message = locale
+            .and_then(Self::message_locale)
+            .unwrap_or(Self::message_());
+        let templates = [
+            ("message", Some(message)),
+            ("migration", Self::migration_()),
+            ("rollback", Self::rollback_()),
+        ];
+        let mut tera = Tera::default();
+        Self::register_filters(&mut tera);
+        tera.add_raw_templates(templates.into_iter().filter_map(|(n, t)| t.map(|t| (n, t))))
+            .map_err(|err| (kind, err))?;
+        let mut cache = cache.write().unwrap_or_else(PoisonError::into_inner);
+        Ok(cache.entry(key).or_insert_with(|| Arc::new(tera)).clone())
+    }
+    // Helper method, not a part of public interface
+    #[doc(hidden)]
+    fn __context(&self) -> Context {
+        let mut context = Self::globals();
+        context.extend(self.into());
+        context
+    }
+    // Helper method, not a part of public interface.
+    // Templates of the same type are parsed once and reused by all its problems.
+    #[doc(hidden)]
+    fn __render_template(&self, name: &'static str) -> Result<String> {
+        let context = self.__context();
+        Self::__tera()?
+            .render(name, &context)
+            .map_err(|err| (Self::kind_(), err).into())
     }
 }
 
@@ -74,16 +316,111 @@ where
         P::kind_()
     }
     fn message(&self) -> Result<String> {
-        self.__render_template(P::message_())
+        self.__render_template("message")
     }
     fn migration(&self) -> Option<Result<String>> {
-        P::migration_().map(|t| self.__render_template(t))
+        P::migration_().map(|_| self.__render_template("migration"))
     }
     fn rollback(&self) -> Option<Result<String>> {
-        if P::migration_().is_some() {
-            P::rollback_().map(|t| self.__render_template(t))
-        } else {
+        P::rollback_().map(|_| self.__render_template("rollback"))
+    }
+    fn severity(&self) -> Severity {
+        P::severity_()
+    }
+    fn applies_to_client(client: &'static str) -> bool {
+        P::applies_to_client_(client)
+    }
+    fn message_in(&self, locale: &str) -> Result<String> {
+        if P::message_locale(locale).is_none() {
+            return self.message();
+        }
+        P::__tera_in(Some(locale))?
+            .render("message", &self.__context())
+            .map_err(|err| (P::kind_(), err).into())
+    }
+    // The context is built, and the templates are taken from the cache only once
+    fn render_all(&self) -> Result<Rendered> {
+        let tera = P::__tera()?;
+        let context = self.__context();
+        let render = |name| {
+            tera.render(name, &context)
+                .map_err(|err| Error::from((P::kind_(), err)))
+        };
+        Ok(Rendered {
+            message: render("message")?,
+            migration: P::migration_().map(|_| render("migration")).transpose()?,
+            rollback: P::rollback_().map(|_| render("rollback")).transpose()?,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod test {
+    use super::*;
+    use crate::client::PostgresClient;
+    use std::collections::BTreeSet;
+
+    // The problem with the message template to be rendered in the empty context
+    struct Message(&'static str);
+    impl Problem for Message {
+        type Client = PostgresClient;
+
+        fn kind(&self) -> &'static str {
+            "message"
+        }
+        fn message(&self) -> Result<String> {
+            Tera::one_off(self.0, &Context::new(), false).map_err(|err| ("message", err).into())
+        }
+        fn migration(&self) -> Option<Result<String>> {
             None
         }
+        fn rollback(&self) -> Option<Result<String>> {
+            None
+        }
+    }
+
+    #[test]
+    fn problem_ord() {
+        let set: BTreeSet<_> = [Message("bar"), Message("foo"), Message("bar")]
+            .into_iter()
+            .map(|p| ProblemOrd::new(p).unwrap())
+            .collect();
+
+        let messages: Vec<_> = set.iter().map(|p| p.problem().0).collect();
+        assert_eq!(messages, vec!["bar", "foo"]);
+    }
+
+    #[test]
+    fn problem_ord_with_render_error() {
+        assert!(ProblemOrd::new(Message("{{ missed }}")).is_err());
+    }
+
+    #[test]
+    fn to_json_value() {
+        let value = Message("some message").to_json_value().unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "kind": "message",
+                "message": "some message",
+                "migration": null,
+                "rollback": null,
+                "severity": "error",
+            })
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Message("some message").display().to_string(),
+            "some message"
+        );
+    }
+
+    #[test]
+    fn display_with_render_error() {
+        assert_eq!(Message("{{ missed }}").display().to_string(), "message");
     }
 }
@@ -1,48 +1,186 @@
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::Debug;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::problem::Problem;
+use crate::severity::Severity;
+
+mod files;
+mod migrations;
 
 /// Collection of problems found in the database.
 #[repr(C)]
 #[derive(Clone, Debug)]
 pub struct Report<P: Problem> {
     problems: Vec<P>,
+    truncated: bool,
+}
+
+/// Combine messages of several (possibly heterogeneous) reports into one text.
+///
+/// ```ignore
+/// let output = merge_messages([tables.messages()?, indexes.messages()?]);
+/// ```
+pub fn merge_messages<I: IntoIterator<Item = Vec<String>>>(messages: I) -> String {
+    messages
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 impl<P: Problem> Default for Report<P> {
     fn default() -> Self {
-        Self { problems: vec![] }
+        Self {
+            problems: vec![],
+            truncated: false,
+        }
     }
 }
 
 impl<P: Problem> Report<P> {
-    pub fn iter(&self) -> Iter<P> {
+    pub fn iter(&self) -> Iter<'_, P> {
         Iter {
             report: self,
             index: 0,
         }
     }
 
+    pub fn as_slice(&self) -> &[P] {
+        &self.problems
+    }
+
+    pub fn into_problems(self) -> Vec<P> {
+        self.problems
+    }
+
+    pub fn get(&self, index: usize) -> Option<&P> {
+        self.problems.get(index)
+    }
+
+    pub fn first(&self) -> Option<&P> {
+        self.problems.first()
+    }
+
+    pub fn last(&self) -> Option<&P> {
+        self.problems.last()
+    }
+
     pub fn insert(&mut self, problem: P) {
         self.problems.push(problem);
     }
 
-    pub fn compact(mut self) -> Self {
-        self.problems.sort_by_key(|a| a.id().unwrap());
-        self.problems.dedup_by_key(|a| a.id().unwrap());
+    /// Whether some problems were skipped because of the limit
+    /// (see `Linter::run_with_limit`).
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    pub(crate) fn truncate(&mut self) {
+        self.truncated = true;
+    }
+
+    /// Keep only the problems satisfying the predicate.
+    pub fn retain(&mut self, f: impl Fn(&P) -> bool) {
+        self.problems.retain(|p| f(p));
+    }
+
+    /// Keep only the problems of the given kinds.
+    pub fn filter_by_kind(mut self, kinds: &[&str]) -> Self {
+        self.retain(|p| kinds.contains(&p.kind()));
         self
     }
 
-    pub fn message(&self) -> Result<String> {
-        let mut output = String::new();
+    /// Split the report into problems having a migration, and the other ones.
+    pub fn partition_migrations(self) -> (Self, Self) {
+        let truncated = self.truncated;
+        let (with, without) = self
+            .problems
+            .into_iter()
+            .partition(|p| p.migration().is_some());
+        (
+            Self {
+                problems: with,
+                truncated,
+            },
+            Self {
+                problems: without,
+                truncated,
+            },
+        )
+    }
+
+    /// Select problems which are absent in the baseline report (compared by ids).
+    pub fn diff(&self, baseline: &Self) -> Result<Self>
+    where
+        P: Clone,
+    {
+        let known = baseline
+            .iter()
+            .map(|p| p.id())
+            .collect::<Result<HashSet<_>>>()?;
+        let mut problems = vec![];
         for problem in self.iter() {
-            if !output.is_empty() {
-                output.push('\n');
+            if !known.contains(&problem.id()?) {
+                problems.push(problem.clone());
             }
-            output.push_str(problem.message()?.as_str());
         }
-        Ok(output)
+        Ok(Self {
+            problems,
+            truncated: self.truncated,
+        })
+    }
+
+    /// Sort problems by a custom key without removing duplicates.
+    /// The sort is stable, so problems with equal keys keep their order.
+    pub fn sort_by<K: Ord>(&mut self, key: impl Fn(&P) -> K) {
+        self.problems.sort_by_key(|p| key(p));
+    }
+
+    /// Sort problems by their fingerprints and remove duplicates.
+    /// Fails if a fingerprint of some problem cannot be rendered.
+    ///
+    /// This is `compact_by` with the fingerprint as the key, except that the key
+    /// is fallible: fingerprints are rendered once upfront to catch the error
+    /// (the infallible key of `compact_by` would make them rendered twice),
+    /// and then sorted and deduplicated in the same way.
+    pub fn compact(self) -> Result<Self> {
+        let truncated = self.truncated;
+        let problems = self
+            .problems
+            .into_iter()
+            .map(|p| p.fingerprint().map(|fingerprint| (fingerprint, p)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            problems: sort_and_dedup(problems),
+            truncated,
+        })
+    }
+
+    /// Sort problems by a custom key and remove the ones with duplicated keys
+    /// (the first problem in the original order is kept).
+    pub fn compact_by<K: Ord>(self, key: impl Fn(&P) -> K) -> Self {
+        let truncated = self.truncated;
+        let problems = self.problems.into_iter().map(|p| (key(&p), p)).collect();
+        Self {
+            problems: sort_and_dedup(problems),
+            truncated,
+        }
+    }
+
+    pub fn message(&self) -> Result<String> {
+        self.messages().map(|messages| messages.join("\n"))
+    }
+
+    /// Rendered messages of all problems in the report.
+    ///
+    /// Reports of different problem types cannot be merged into one `Report`
+    /// (the `Problem` trait isn't object-safe), but their messages can be combined
+    /// for a single summary via `merge_messages`.
+    pub fn messages(&self) -> Result<Vec<String>> {
+        self.iter().map(|problem| problem.message()).collect()
     }
 
     pub fn migration(&self) -> Result<String> {
@@ -58,9 +196,11 @@ impl<P: Problem> Report<P> {
         Ok(output)
     }
 
+    /// Rollbacks of the migrations in the reverse order
+    /// to undo the changes made by the `migration`.
     pub fn rollback(&self) -> Result<String> {
         let mut output = String::new();
-        for problem in self.iter() {
+        for problem in self.problems.iter().rev() {
             if let Some(rollback) = problem.rollback() {
                 if !output.is_empty() {
                     output.push('\n');
@@ -71,6 +211,219 @@ impl<P: Problem> Report<P> {
         Ok(output)
     }
 
+    /// The migration wrapped into a transaction (empty if there is nothing to migrate).
+    ///
+    /// Neither this method nor `migration` runs anything. It's up to the caller
+    /// to choose whether the statements should be applied atomically or one by one
+    /// (some statements like `CREATE INDEX CONCURRENTLY` cannot run inside a transaction).
+    pub fn migration_transaction(&self) -> Result<String> {
+        self.migration().map(|sql| wrap_transaction(&sql))
+    }
+
+    /// The rollback wrapped into a transaction (empty if there is nothing to roll back).
+    ///
+    /// Like `migration_transaction`, the caller is responsible for choosing
+    /// between this one and the plain `rollback`.
+    pub fn rollback_transaction(&self) -> Result<String> {
+        self.rollback().map(|sql| wrap_transaction(&sql))
+    }
+
+    /// Serialize the rendered problems into a JSON array of objects
+    /// with `kind`, `message`, `migration` and `rollback` fields.
+    pub fn to_json(&self) -> Result<String> {
+        let list = self
+            .iter()
+            .map(RenderedProblem::new)
+            .collect::<Result<Vec<_>>>()?;
+        serde_json::to_string(&list).map_err(Error::SerializeJson)
+    }
+
+    /// Serialize the rendered problems into TOML as an array of `problems` tables
+    /// with `kind`, `message`, and optional `migration` and `rollback` fields.
+    pub fn to_toml(&self) -> Result<String> {
+        #[derive(Serialize)]
+        struct Document {
+            problems: Vec<RenderedProblem>,
+        }
+
+        let problems = self
+            .iter()
+            .map(RenderedProblem::new)
+            .collect::<Result<Vec<_>>>()?;
+        toml::to_string(&Document { problems }).map_err(Error::SerializeToml)
+    }
+
+    /// Serialize the report into the SARIF 2.1.0 log to be consumed by code scanning tools.
+    /// Every kind of problems becomes a rule, and every problem becomes a result of its rule.
+    pub fn to_sarif(&self) -> Result<String> {
+        let mut rules: Vec<serde_json::Value> = vec![];
+        let mut results: Vec<serde_json::Value> = vec![];
+        for (index, (kind, problems)) in self.group_by_kind().into_iter().enumerate() {
+            let mut rule = json!({
+                "id": kind,
+                "name": kind,
+                "properties": {
+                    "migration": problems.iter().any(|p| p.migration().is_some()),
+                },
+            });
+            if let Some(url) = problems.iter().find_map(|p| p.docs_url()) {
+                rule["helpUri"] = url.into();
+            }
+            rules.push(rule);
+            for problem in problems {
+                let level = match problem.severity() {
+                    Severity::Info => "note",
+                    Severity::Warning => "warning",
+                    Severity::Error => "error",
+                };
+                results.push(json!({
+                    "ruleId": kind,
+                    "ruleIndex": index,
+                    "level": level,
+                    "message": { "text": problem.message()? },
+                }));
+            }
+        }
+        let log = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": { "driver": { "name": "dblinter", "rules": rules } },
+                "results": results,
+            }],
+        });
+        serde_json::to_string(&log).map_err(Error::SerializeJson)
+    }
+
+    /// Serialize the report into the JUnit XML where every kind of problems
+    /// is a test case, and every problem is a failure of its test case.
+    /// Every test case has failures, so the suite counts as many failures as tests.
+    pub fn to_junit(&self) -> Result<String> {
+        let groups = self.group_by_kind();
+        let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        output.push_str(&format!(
+            "<testsuite name=\"dblinter\" tests=\"{}\" failures=\"{}\">\n",
+            groups.len(),
+            groups.len(),
+        ));
+        for (kind, problems) in groups {
+            output.push_str(&format!(
+                "  <testcase name=\"{}\" classname=\"dblinter\">\n",
+                escape_xml(kind),
+            ));
+            for problem in problems {
+                let message = escape_xml(&problem.message()?);
+                output.push_str(&format!(
+                    "    <failure message=\"{}\">{}</failure>\n",
+                    message, message,
+                ));
+            }
+            output.push_str("  </testcase>\n");
+        }
+        output.push_str("</testsuite>\n");
+        Ok(output)
+    }
+
+    /// Render the report into a self-contained HTML table
+    /// with a separate section (`tbody`) for every kind of problems.
+    pub fn to_html(&self) -> Result<String> {
+        let mut output = String::from("<table class=\"dblinter\">\n");
+        output.push_str("<thead><tr><th>Message</th><th>Migration</th></tr></thead>\n");
+        for (kind, problems) in self.group_by_kind() {
+            output.push_str("<tbody>\n");
+            output.push_str(&format!(
+                "<tr><th colspan=\"2\">{}</th></tr>\n",
+                escape_xml(kind),
+            ));
+            for problem in problems {
+                let migration = match problem.migration() {
+                    Some(_) => "yes",
+                    None => "no",
+                };
+                output.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td></tr>\n",
+                    escape_xml(&problem.message()?),
+                    migration,
+                ));
+            }
+            output.push_str("</tbody>\n");
+        }
+        output.push_str("</table>\n");
+        Ok(output)
+    }
+
+    /// Serialize the report into CSV with a header
+    /// and columns `kind`, `message`, `has_migration`, `has_rollback`.
+    pub fn to_csv(&self) -> Result<String> {
+        let mut output = String::from("kind,message,has_migration,has_rollback\n");
+        for problem in self.iter() {
+            output.push_str(&format!(
+                "{},{},{},{}\n",
+                escape_csv(problem.kind()),
+                escape_csv(&problem.message()?),
+                problem.migration().is_some(),
+                problem.rollback().is_some(),
+            ));
+        }
+        Ok(output)
+    }
+
+    /// Render every problem in a line with its kind and the sign of available migration.
+    /// When `color` is set, the kind is highlighted according to the problem severity.
+    #[cfg(feature = "color")]
+    pub fn to_terminal(&self, color: bool) -> Result<String> {
+        use yansi::Paint;
+
+        let mut output = String::new();
+        for problem in self.iter() {
+            let kind = problem.kind();
+            let message = problem.message()?;
+            let migration = problem.migration().is_some();
+            let (sign, fix) = if migration {
+                ("+", "migration available")
+            } else {
+                ("-", "no migration")
+            };
+            let line = if color {
+                let kind = match problem.severity() {
+                    Severity::Info => kind.blue(),
+                    Severity::Warning => kind.yellow(),
+                    Severity::Error => kind.red(),
+                };
+                let fix = if migration { fix.green() } else { fix.dim() };
+                format!("{} {}: {} ({})\n", sign, kind.bold(), message, fix)
+            } else {
+                format!("{} {}: {} ({})\n", sign, kind, message, fix)
+            };
+            output.push_str(&line);
+        }
+        Ok(output)
+    }
+
+    /// Group problems by their kind preserving the order of problems in every group.
+    pub fn group_by_kind(&self) -> BTreeMap<&'static str, Vec<&P>> {
+        let mut groups: BTreeMap<&'static str, Vec<&P>> = BTreeMap::new();
+        for problem in self.iter() {
+            groups.entry(problem.kind()).or_default().push(problem);
+        }
+        groups
+    }
+
+    /// Group problems by their category preserving the order of problems in every group
+    /// (uncategorized problems are grouped under the empty key).
+    pub fn group_by_category(&self) -> BTreeMap<&'static str, Vec<&P>> {
+        let mut groups: BTreeMap<&'static str, Vec<&P>> = BTreeMap::new();
+        for problem in self.iter() {
+            groups.entry(problem.category()).or_default().push(problem);
+        }
+        groups
+    }
+
+    /// Check whether the report contains a problem of the given kind.
+    pub fn contains(&self, kind: &str) -> bool {
+        self.iter().any(|p| p.kind() == kind)
+    }
+
     pub fn count(&self) -> usize {
         self.problems.len()
     }
@@ -85,6 +438,81 @@ impl<P: Problem> Report<P> {
             .filter(|p| p.migration().is_some())
             .count()
     }
+
+    pub fn count_rollbacks(&self) -> usize {
+        self.problems
+            .iter()
+            .filter(|p| p.rollback().is_some())
+            .count()
+    }
+
+    /// Collect all the counters in a single pass.
+    pub fn summary(&self) -> Summary {
+        let mut summary = Summary::default();
+        for problem in self.iter() {
+            summary.total += 1;
+            if problem.migration().is_some() {
+                summary.migrations += 1;
+            }
+            if problem.rollback().is_some() {
+                summary.rollbacks += 1;
+            }
+            *summary.by_kind.entry(problem.kind()).or_default() += 1;
+        }
+        summary
+    }
+
+    pub fn count_by_severity(&self) -> BTreeMap<Severity, usize> {
+        let mut counts = BTreeMap::new();
+        for problem in self.iter() {
+            *counts.entry(problem.severity()).or_default() += 1;
+        }
+        counts
+    }
+
+    /// The highest severity of problems in the report (`None` for an empty report).
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.iter().map(|p| p.severity()).max()
+    }
+
+    pub fn count_by_kind(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for problem in self.iter() {
+            *counts.entry(problem.kind()).or_default() += 1;
+        }
+        counts
+    }
+}
+
+/// Totals of the report.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Summary {
+    pub total: usize,
+    pub migrations: usize,
+    pub rollbacks: usize,
+    pub by_kind: BTreeMap<&'static str, usize>,
+}
+
+// The serializable representation of a problem with all its templates rendered.
+#[derive(Clone, Debug, Serialize)]
+struct RenderedProblem {
+    kind: &'static str,
+    message: String,
+    migration: Option<String>,
+    rollback: Option<String>,
+}
+
+impl RenderedProblem {
+    fn new<P: Problem>(problem: &P) -> Result<Self> {
+        let rendered = problem.render_all()?;
+        Ok(Self {
+            kind: problem.kind(),
+            message: rendered.message,
+            migration: rendered.migration,
+            rollback: rendered.rollback,
+        })
+    }
 }
 
 #[repr(C)]
@@ -108,6 +536,80 @@ impl<'a, P: Problem> Iterator for Iter<'a, P> {
     }
 }
 
+impl<P: Problem> FromIterator<P> for Report<P> {
+    fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self {
+        Self {
+            problems: iter.into_iter().collect(),
+            truncated: false,
+        }
+    }
+}
+
+impl<P: Problem> Extend<P> for Report<P> {
+    fn extend<I: IntoIterator<Item = P>>(&mut self, iter: I) {
+        self.problems.extend(iter);
+    }
+}
+
+impl<'a, P: Problem> IntoIterator for &'a Report<P> {
+    type Item = &'a P;
+    type IntoIter = Iter<'a, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<P: Problem> IntoIterator for Report<P> {
+    type Item = P;
+    type IntoIter = std::vec::IntoIter<P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.problems.into_iter()
+    }
+}
+
+// Wrap non-empty SQL statements into a transaction.
+fn wrap_transaction(sql: &str) -> String {
+    if sql.is_empty() {
+        String::new()
+    } else {
+        format!("BEGIN;\n{}\nCOMMIT;", sql)
+    }
+}
+
+// Sort values by their keys (stable) and remove the values with duplicated keys.
+fn sort_and_dedup<K: Ord, V>(mut list: Vec<(K, V)>) -> Vec<V> {
+    list.sort_by(|(a, _), (b, _)| a.cmp(b));
+    list.dedup_by(|(a, _), (b, _)| a == b);
+    list.into_iter().map(|(_, v)| v).collect()
+}
+
+// Escape special characters to be used in XML (HTML) attributes and texts.
+fn escape_xml(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&apos;"),
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+// Quote a CSV value if it contains a separator, a quotation mark or a line break.
+fn escape_csv(input: &str) -> String {
+    if input.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", input.replace('"', "\"\""))
+    } else {
+        input.to_string()
+    }
+}
+
 #[cfg(all(test, feature = "postgres"))]
 mod test {
     use super::*;
@@ -116,18 +618,18 @@ mod test {
     use postgres_from_row::FromRow;
 
     #[repr(C)]
-    #[derive(Debug, FromRow)]
-    struct Item {
-        kind: &'static str,
-        message: String,
-        migration: Option<String>,
-        rollback: Option<String>,
+    #[derive(Clone, Debug, FromRow)]
+    pub(super) struct Item {
+        pub(super) kind: &'static str,
+        pub(super) message: String,
+        pub(super) migration: Option<String>,
+        pub(super) rollback: Option<String>,
     }
     impl Problem for Item {
         type Client = PostgresClient;
 
         fn kind(&self) -> &'static str {
-            &self.kind
+            self.kind
         }
         fn message(&self) -> Result<String> {
             Ok(self.message.clone())
@@ -140,6 +642,131 @@ mod test {
         }
     }
 
+    // The problem with a custom severity
+    #[derive(Clone, Debug)]
+    struct Rated {
+        kind: &'static str,
+        severity: Severity,
+    }
+    impl Problem for Rated {
+        type Client = PostgresClient;
+
+        fn kind(&self) -> &'static str {
+            self.kind
+        }
+        fn message(&self) -> Result<String> {
+            Ok(self.kind.to_string())
+        }
+        fn migration(&self) -> Option<Result<String>> {
+            None
+        }
+        fn rollback(&self) -> Option<Result<String>> {
+            None
+        }
+        fn severity(&self) -> Severity {
+            self.severity
+        }
+    }
+
+    // The problem with a category and tags
+    struct Tagged {
+        kind: &'static str,
+        category: &'static str,
+    }
+    impl Problem for Tagged {
+        type Client = PostgresClient;
+
+        fn kind(&self) -> &'static str {
+            self.kind
+        }
+        fn message(&self) -> Result<String> {
+            Ok(self.kind.to_string())
+        }
+        fn migration(&self) -> Option<Result<String>> {
+            None
+        }
+        fn rollback(&self) -> Option<Result<String>> {
+            None
+        }
+        fn category(&self) -> &'static str {
+            self.category
+        }
+        fn tags(&self) -> &'static [&'static str] {
+            &["schema"]
+        }
+    }
+
+    // The problem with an optional link to the docs
+    struct Documented {
+        kind: &'static str,
+        docs_url: Option<&'static str>,
+    }
+    impl Problem for Documented {
+        type Client = PostgresClient;
+
+        fn kind(&self) -> &'static str {
+            self.kind
+        }
+        fn message(&self) -> Result<String> {
+            Ok(self.kind.to_string())
+        }
+        fn migration(&self) -> Option<Result<String>> {
+            None
+        }
+        fn rollback(&self) -> Option<Result<String>> {
+            None
+        }
+        fn docs_url(&self) -> Option<&'static str> {
+            self.docs_url
+        }
+    }
+
+    // The problem identified by the name of the object
+    struct Named {
+        name: &'static str,
+        message: &'static str,
+    }
+    impl Problem for Named {
+        type Client = PostgresClient;
+
+        fn kind(&self) -> &'static str {
+            "named"
+        }
+        fn message(&self) -> Result<String> {
+            Ok(self.message.to_string())
+        }
+        fn migration(&self) -> Option<Result<String>> {
+            None
+        }
+        fn rollback(&self) -> Option<Result<String>> {
+            None
+        }
+        fn fingerprint(&self) -> Result<String> {
+            Ok(format!("{}:{}", self.kind(), self.name))
+        }
+    }
+
+    // The problem whose message cannot be rendered
+    #[derive(Clone, Debug)]
+    struct Broken {}
+    impl Problem for Broken {
+        type Client = PostgresClient;
+
+        fn kind(&self) -> &'static str {
+            "broken"
+        }
+        fn message(&self) -> Result<String> {
+            tera::Tera::one_off("{{ missed }}", &tera::Context::new(), false)
+                .map_err(|err| ("message", err).into())
+        }
+        fn migration(&self) -> Option<Result<String>> {
+            None
+        }
+        fn rollback(&self) -> Option<Result<String>> {
+            None
+        }
+    }
+
     #[test]
     fn iter() {
         let report = Report {
@@ -157,6 +784,7 @@ mod test {
                     rollback: None,
                 },
             ],
+            truncated: false,
         };
 
         let mut iter = report.iter();
@@ -182,16 +810,17 @@ mod test {
                     rollback: None,
                 },
             ],
+            truncated: false,
         };
 
-        let mut iter = report.iter();
+        let mut iter = report.into_iter();
         assert_eq!(iter.next().unwrap().kind, "foo".to_string());
         assert_eq!(iter.next().unwrap().kind, "bar".to_string());
         assert!(iter.next().is_none());
     }
 
     #[test]
-    fn compact() {
+    fn ref_into_iter() {
         let report = Report {
             problems: vec![
                 Item {
@@ -206,25 +835,110 @@ mod test {
                     migration: None,
                     rollback: None,
                 },
+            ],
+            truncated: false,
+        };
+
+        let mut kinds = vec![];
+        for problem in &report {
+            kinds.push(problem.kind);
+        }
+        assert_eq!(kinds, vec!["foo", "bar"]);
+        assert_eq!(report.count(), 2);
+    }
+
+    #[test]
+    fn indexed_access() {
+        let report = Report {
+            problems: vec![
                 Item {
                     kind: "foo",
                     message: "some foo".to_string(),
                     migration: None,
                     rollback: None,
                 },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "baz",
+                    message: "some baz".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
             ],
+            truncated: false,
         };
 
-        let report = report.compact();
+        assert_eq!(report.get(1).unwrap().kind, "bar");
+        assert!(report.get(3).is_none());
+        assert_eq!(report.first().unwrap().kind, "foo");
+        assert_eq!(report.last().unwrap().kind, "baz");
 
-        let mut iter = report.iter();
-        assert_eq!(iter.next().unwrap().kind, "bar".to_string());
+        let report = Report::<Item>::default();
+        assert!(report.first().is_none());
+        assert!(report.last().is_none());
+    }
+
+    #[test]
+    fn as_slice_and_into_problems() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "some foo".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        let slice = report.as_slice();
+        assert_eq!(slice.len(), 2);
+        assert_eq!(slice[1].kind, "bar");
+
+        let problems = report.into_problems();
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].kind, "foo");
+    }
+
+    #[test]
+    fn from_iter() {
+        let problems = vec![
+            Item {
+                kind: "foo",
+                message: "some foo".to_string(),
+                migration: None,
+                rollback: None,
+            },
+            Item {
+                kind: "bar",
+                message: "some bar".to_string(),
+                migration: None,
+                rollback: None,
+            },
+        ];
+
+        let report: Report<Item> = problems.into_iter().collect();
+
+        let mut iter = report.iter();
         assert_eq!(iter.next().unwrap().kind, "foo".to_string());
+        assert_eq!(iter.next().unwrap().kind, "bar".to_string());
         assert!(iter.next().is_none());
     }
 
     #[test]
-    fn insert() {
+    fn extend() {
         let mut report = Report {
             problems: vec![Item {
                 kind: "foo",
@@ -232,16 +946,15 @@ mod test {
                 migration: None,
                 rollback: None,
             }],
+            truncated: false,
         };
 
-        let item = Item {
+        report.extend(vec![Item {
             kind: "bar",
             message: "some bar".to_string(),
             migration: None,
             rollback: None,
-        };
-
-        report.insert(item);
+        }]);
 
         let mut iter = report.iter();
         assert_eq!(iter.next().unwrap().kind, "foo".to_string());
@@ -250,7 +963,7 @@ mod test {
     }
 
     #[test]
-    fn message() {
+    fn compact() {
         let report = Report {
             problems: vec![
                 Item {
@@ -265,14 +978,68 @@ mod test {
                     migration: None,
                     rollback: None,
                 },
+                Item {
+                    kind: "foo",
+                    message: "some foo".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
             ],
+            truncated: false,
         };
 
-        assert_eq!(report.message().unwrap(), "some foo\nsome bar");
+        let report = report.compact().unwrap();
+
+        let mut iter = report.iter();
+        assert_eq!(iter.next().unwrap().kind, "bar".to_string());
+        assert_eq!(iter.next().unwrap().kind, "foo".to_string());
+        assert!(iter.next().is_none());
     }
 
     #[test]
-    fn migration() {
+    fn compact_by_fingerprint() {
+        let report = Report::from_iter(vec![
+            Named {
+                name: "users",
+                message: "first message",
+            },
+            Named {
+                name: "orders",
+                message: "some message",
+            },
+            Named {
+                name: "users",
+                message: "second message",
+            },
+        ]);
+
+        let report = report.compact().unwrap();
+
+        assert_eq!(report.message().unwrap(), "some message\nfirst message");
+    }
+
+    #[test]
+    fn compact_with_different_rollbacks() {
+        let report = Report::from_iter(vec![
+            Item {
+                kind: "foo",
+                message: "some foo".to_string(),
+                migration: Some("CREATE INDEX foo;".to_string()),
+                rollback: Some("DROP INDEX foo;".to_string()),
+            },
+            Item {
+                kind: "foo",
+                message: "some foo".to_string(),
+                migration: Some("CREATE INDEX foo;".to_string()),
+                rollback: Some("DROP INDEX IF EXISTS foo;".to_string()),
+            },
+        ]);
+
+        assert_eq!(report.compact().unwrap().count(), 2);
+    }
+
+    #[test]
+    fn partition_migrations() {
         let report = Report {
             problems: vec![
                 Item {
@@ -293,30 +1060,28 @@ mod test {
                     migration: Some("baz migration".to_string()),
                     rollback: None,
                 },
-                Item {
-                    kind: "qux",
-                    message: "some qux".to_string(),
-                    migration: Some("qux migration".to_string()),
-                    rollback: Some("qux rollback".to_string()),
-                },
             ],
+            truncated: false,
         };
 
-        assert_eq!(
-            report.migration().unwrap(),
-            "foo migration\nbaz migration\nqux migration"
-        );
+        let (fixable, other) = report.partition_migrations();
+
+        assert_eq!(fixable.count(), 2);
+        assert_eq!(fixable.migration().unwrap(), "foo migration\nbaz migration");
+        assert_eq!(other.count(), 1);
+        assert_eq!(other.first().unwrap().kind, "bar");
+        assert_eq!(other.migration().unwrap(), "");
     }
 
     #[test]
-    fn rollback() {
-        let report = Report {
+    fn diff() {
+        let baseline = Report {
             problems: vec![
                 Item {
                     kind: "foo",
                     message: "some foo".to_string(),
-                    migration: Some("foo migration".to_string()),
-                    rollback: Some("foo rollback".to_string()),
+                    migration: None,
+                    rollback: None,
                 },
                 Item {
                     kind: "bar",
@@ -324,33 +1089,206 @@ mod test {
                     migration: None,
                     rollback: None,
                 },
+            ],
+            truncated: false,
+        };
+        let report = Report {
+            problems: vec![
                 Item {
                     kind: "baz",
                     message: "some baz".to_string(),
-                    migration: Some("baz migration".to_string()),
+                    migration: None,
                     rollback: None,
                 },
                 Item {
-                    kind: "qux",
-                    message: "some qux".to_string(),
-                    migration: Some("qux migration".to_string()),
-                    rollback: Some("qux rollback".to_string()),
+                    kind: "foo",
+                    message: "some foo".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "bar",
+                    message: "another bar".to_string(),
+                    migration: None,
+                    rollback: None,
                 },
             ],
+            truncated: false,
+        };
+
+        let diff = report.diff(&baseline).unwrap();
+
+        let mut iter = diff.iter();
+        assert_eq!(iter.next().unwrap().message, "some baz");
+        assert_eq!(iter.next().unwrap().message, "another bar");
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn diff_with_render_error() {
+        let baseline = Report::default();
+        let report = Report {
+            problems: vec![Broken {}],
+            truncated: false,
         };
 
-        assert_eq!(report.rollback().unwrap(), "foo rollback\nqux rollback");
+        assert!(report.diff(&baseline).is_err());
     }
 
     #[test]
-    fn counters() {
+    fn sort_by() {
+        let mut report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "foo b".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "bar",
+                    message: "bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "foo",
+                    message: "foo a".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "foo",
+                    message: "foo b".to_string(),
+                    migration: Some("foo migration".to_string()),
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        report.sort_by(|p| (p.kind, p.message.clone()));
+
+        let mut iter = report.iter();
+        assert_eq!(iter.next().unwrap().message, "bar");
+        assert_eq!(iter.next().unwrap().message, "foo a");
+        let item = iter.next().unwrap();
+        assert_eq!(item.message, "foo b");
+        assert!(item.migration.is_none());
+        let item = iter.next().unwrap();
+        assert_eq!(item.message, "foo b");
+        assert!(item.migration.is_some());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn compact_by() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "first foo".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "foo",
+                    message: "second foo".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        let report = report.compact_by(|p| p.kind);
+
+        let mut iter = report.iter();
+        assert_eq!(iter.next().unwrap().message, "some bar");
+        assert_eq!(iter.next().unwrap().message, "first foo");
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn compact_with_render_error() {
         let report = Report {
+            problems: vec![Broken {}],
+            truncated: false,
+        };
+
+        let error = report.compact().unwrap_err();
+
+        assert!(matches!(error, Error::RenderTemplate("message", _)));
+    }
+
+    #[test]
+    fn insert() {
+        let mut report = Report {
+            problems: vec![Item {
+                kind: "foo",
+                message: "some foo".to_string(),
+                migration: None,
+                rollback: None,
+            }],
+            truncated: false,
+        };
+
+        let item = Item {
+            kind: "bar",
+            message: "some bar".to_string(),
+            migration: None,
+            rollback: None,
+        };
+
+        report.insert(item);
+
+        let mut iter = report.iter();
+        assert_eq!(iter.next().unwrap().kind, "foo".to_string());
+        assert_eq!(iter.next().unwrap().kind, "bar".to_string());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn retain() {
+        let mut report = Report {
             problems: vec![
                 Item {
                     kind: "foo",
                     message: "some foo".to_string(),
                     migration: Some("foo migration".to_string()),
-                    rollback: Some("foo rollback".to_string()),
+                    rollback: None,
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        report.retain(|p| p.migration.is_none());
+
+        assert_eq!(report.count(), 1);
+        assert_eq!(report.iter().next().unwrap().kind, "bar");
+    }
+
+    #[test]
+    fn filter_by_kind() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "some foo".to_string(),
+                    migration: None,
+                    rollback: None,
                 },
                 Item {
                     kind: "bar",
@@ -361,20 +1299,754 @@ mod test {
                 Item {
                     kind: "baz",
                     message: "some baz".to_string(),
-                    migration: Some("baz migration".to_string()),
+                    migration: None,
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        let report = report.filter_by_kind(&["foo", "baz"]);
+
+        assert_eq!(report.count(), 2);
+        let mut iter = report.iter();
+        assert_eq!(iter.next().unwrap().kind, "foo");
+        assert_eq!(iter.next().unwrap().kind, "baz");
+        assert!(iter.next().is_none());
+
+        let report = report.filter_by_kind(&["qux"]);
+
+        assert!(report.is_empty());
+        assert_eq!(report.count(), 0);
+    }
+
+    #[test]
+    fn message() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "some foo".to_string(),
+                    migration: None,
                     rollback: None,
                 },
                 Item {
-                    kind: "qux",
-                    message: "some qux".to_string(),
-                    migration: Some("qux migration".to_string()),
-                    rollback: Some("qux rollback".to_string()),
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
                 },
             ],
+            truncated: false,
         };
 
-        assert_eq!(report.is_empty(), false);
+        assert_eq!(report.message().unwrap(), "some foo\nsome bar");
+    }
+
+    #[test]
+    fn merge_messages() {
+        let items = Report::from_iter(vec![Item {
+            kind: "foo",
+            message: "some foo".to_string(),
+            migration: None,
+            rollback: None,
+        }]);
+        let rated = Report::from_iter(vec![Rated {
+            kind: "bar",
+            severity: Severity::Info,
+        }]);
+
+        assert_eq!(
+            super::merge_messages([items.messages().unwrap(), rated.messages().unwrap()]),
+            "some foo\nbar"
+        );
+    }
+
+    #[test]
+    fn migration() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "some foo".to_string(),
+                    migration: Some("foo migration".to_string()),
+                    rollback: Some("foo rollback".to_string()),
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "baz",
+                    message: "some baz".to_string(),
+                    migration: Some("baz migration".to_string()),
+                    rollback: None,
+                },
+                Item {
+                    kind: "qux",
+                    message: "some qux".to_string(),
+                    migration: Some("qux migration".to_string()),
+                    rollback: Some("qux rollback".to_string()),
+                },
+            ],
+            truncated: false,
+        };
+
+        assert_eq!(
+            report.migration().unwrap(),
+            "foo migration\nbaz migration\nqux migration"
+        );
+    }
+
+    #[test]
+    fn rollback() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "some foo".to_string(),
+                    migration: Some("foo migration".to_string()),
+                    rollback: Some("foo rollback".to_string()),
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "baz",
+                    message: "some baz".to_string(),
+                    migration: Some("baz migration".to_string()),
+                    rollback: None,
+                },
+                Item {
+                    kind: "qux",
+                    message: "some qux".to_string(),
+                    migration: Some("qux migration".to_string()),
+                    rollback: Some("qux rollback".to_string()),
+                },
+            ],
+            truncated: false,
+        };
+
+        assert_eq!(report.rollback().unwrap(), "qux rollback\nfoo rollback");
+    }
+
+    #[test]
+    fn reversed_rollback() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "some foo".to_string(),
+                    migration: Some("CREATE TABLE foo".to_string()),
+                    rollback: Some("DROP TABLE foo".to_string()),
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: Some("ALTER TABLE foo ADD bar".to_string()),
+                    rollback: Some("ALTER TABLE foo DROP bar".to_string()),
+                },
+                Item {
+                    kind: "baz",
+                    message: "some baz".to_string(),
+                    migration: Some("CREATE INDEX baz ON foo (bar)".to_string()),
+                    rollback: Some("DROP INDEX baz".to_string()),
+                },
+            ],
+            truncated: false,
+        };
+
+        assert_eq!(
+            report.migration().unwrap(),
+            "CREATE TABLE foo\nALTER TABLE foo ADD bar\nCREATE INDEX baz ON foo (bar)"
+        );
+        assert_eq!(
+            report.rollback().unwrap(),
+            "DROP INDEX baz\nALTER TABLE foo DROP bar\nDROP TABLE foo"
+        );
+    }
+
+    #[test]
+    fn to_json() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "some foo".to_string(),
+                    migration: Some("foo migration".to_string()),
+                    rollback: Some("foo rollback".to_string()),
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        let output: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+
+        assert_eq!(
+            output,
+            serde_json::json!([
+                {
+                    "kind": "foo",
+                    "message": "some foo",
+                    "migration": "foo migration",
+                    "rollback": "foo rollback",
+                },
+                {
+                    "kind": "bar",
+                    "message": "some bar",
+                    "migration": null,
+                    "rollback": null,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn to_toml() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "some \"foo\"".to_string(),
+                    migration: Some("foo migration".to_string()),
+                    rollback: Some("foo rollback".to_string()),
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        assert_eq!(
+            report.to_toml().unwrap(),
+            "[[problems]]\n\
+             kind = \"foo\"\n\
+             message = 'some \"foo\"'\n\
+             migration = \"foo migration\"\n\
+             rollback = \"foo rollback\"\n\
+             \n\
+             [[problems]]\n\
+             kind = \"bar\"\n\
+             message = \"some bar\"\n"
+        );
+    }
+
+    #[test]
+    fn to_sarif() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "some foo".to_string(),
+                    migration: Some("foo migration".to_string()),
+                    rollback: None,
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        let output: serde_json::Value = serde_json::from_str(&report.to_sarif().unwrap()).unwrap();
+
+        assert_eq!(output["version"], "2.1.0");
+        assert!(output["$schema"].is_string());
+        let run = &output["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "dblinter");
+        assert_eq!(
+            run["tool"]["driver"]["rules"],
+            serde_json::json!([
+                { "id": "bar", "name": "bar", "properties": { "migration": false } },
+                { "id": "foo", "name": "foo", "properties": { "migration": true } },
+            ])
+        );
+        assert_eq!(
+            run["results"],
+            serde_json::json!([
+                {
+                    "ruleId": "bar",
+                    "ruleIndex": 0,
+                    "level": "error",
+                    "message": { "text": "some bar" },
+                },
+                {
+                    "ruleId": "foo",
+                    "ruleIndex": 1,
+                    "level": "error",
+                    "message": { "text": "some foo" },
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn to_sarif_with_docs_url() {
+        let report = Report::from_iter(vec![
+            Documented {
+                kind: "foo",
+                docs_url: Some("https://example.com/rules/foo"),
+            },
+            Documented {
+                kind: "bar",
+                docs_url: None,
+            },
+        ]);
+
+        let output: serde_json::Value = serde_json::from_str(&report.to_sarif().unwrap()).unwrap();
+
+        let rules = &output["runs"][0]["tool"]["driver"]["rules"];
+        assert!(rules[0].get("helpUri").is_none());
+        assert_eq!(rules[1]["helpUri"], "https://example.com/rules/foo");
+    }
+
+    #[test]
+    fn to_junit() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "first <foo> & \"bar\"".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "foo",
+                    message: "second foo".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        assert_eq!(
+            report.to_junit().unwrap(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <testsuite name=\"dblinter\" tests=\"2\" failures=\"2\">\n\
+             \x20 <testcase name=\"bar\" classname=\"dblinter\">\n\
+             \x20   <failure message=\"some bar\">some bar</failure>\n\
+             \x20 </testcase>\n\
+             \x20 <testcase name=\"foo\" classname=\"dblinter\">\n\
+             \x20   <failure message=\"first &lt;foo&gt; &amp; &quot;bar&quot;\">\
+                     first &lt;foo&gt; &amp; &quot;bar&quot;</failure>\n\
+             \x20   <failure message=\"second foo\">second foo</failure>\n\
+             \x20 </testcase>\n\
+             </testsuite>\n"
+        );
+    }
+
+    #[test]
+    fn to_html() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "a < b && b > c".to_string(),
+                    migration: Some("foo migration".to_string()),
+                    rollback: None,
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        assert_eq!(
+            report.to_html().unwrap(),
+            "<table class=\"dblinter\">\n\
+             <thead><tr><th>Message</th><th>Migration</th></tr></thead>\n\
+             <tbody>\n\
+             <tr><th colspan=\"2\">bar</th></tr>\n\
+             <tr><td>some bar</td><td>no</td></tr>\n\
+             </tbody>\n\
+             <tbody>\n\
+             <tr><th colspan=\"2\">foo</th></tr>\n\
+             <tr><td>a &lt; b &amp;&amp; b &gt; c</td><td>yes</td></tr>\n\
+             </tbody>\n\
+             </table>\n"
+        );
+    }
+
+    #[test]
+    fn to_csv() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "some \"foo\", and\nmore".to_string(),
+                    migration: Some("foo migration".to_string()),
+                    rollback: Some("foo rollback".to_string()),
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: Some("bar migration".to_string()),
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        assert_eq!(
+            report.to_csv().unwrap(),
+            "kind,message,has_migration,has_rollback\n\
+             foo,\"some \"\"foo\"\", and\nmore\",true,true\n\
+             bar,some bar,true,false\n"
+        );
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn to_terminal() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "some foo".to_string(),
+                    migration: Some("foo migration".to_string()),
+                    rollback: None,
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        assert_eq!(
+            report.to_terminal(false).unwrap(),
+            "+ foo: some foo (migration available)\n\
+             - bar: some bar (no migration)\n"
+        );
+
+        let colored = report.to_terminal(true).unwrap();
+        assert!(colored.contains("\x1b["));
+        assert!(colored.contains("some foo"));
+    }
+
+    #[test]
+    fn group_by_kind() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "first foo".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "foo",
+                    message: "second foo".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        let groups = report.group_by_kind();
+
+        assert_eq!(groups.keys().collect::<Vec<_>>(), vec![&"bar", &"foo"]);
+        assert_eq!(groups["bar"].len(), 1);
+        assert_eq!(groups["bar"][0].message, "some bar");
+        assert_eq!(groups["foo"].len(), 2);
+        assert_eq!(groups["foo"][0].message, "first foo");
+        assert_eq!(groups["foo"][1].message, "second foo");
+    }
+
+    #[test]
+    fn group_by_category() {
+        let report = Report::from_iter(vec![
+            Tagged {
+                kind: "foo",
+                category: "safety",
+            },
+            Tagged {
+                kind: "bar",
+                category: "performance",
+            },
+            Tagged {
+                kind: "baz",
+                category: "safety",
+            },
+            Tagged {
+                kind: "qux",
+                category: "",
+            },
+        ]);
+
+        let groups = report.group_by_category();
+
+        assert_eq!(
+            groups.keys().collect::<Vec<_>>(),
+            vec![&"", &"performance", &"safety"]
+        );
+        assert_eq!(groups["performance"][0].kind, "bar");
+        assert_eq!(groups["safety"][0].kind, "foo");
+        assert_eq!(groups["safety"][1].kind, "baz");
+        assert_eq!(groups[""][0].tags(), &["schema"]);
+    }
+
+    #[test]
+    fn transactions() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "some foo".to_string(),
+                    migration: Some("foo migration".to_string()),
+                    rollback: Some("foo rollback".to_string()),
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: Some("bar migration".to_string()),
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        assert_eq!(
+            report.migration_transaction().unwrap(),
+            "BEGIN;\nfoo migration\nbar migration\nCOMMIT;"
+        );
+        assert_eq!(
+            report.rollback_transaction().unwrap(),
+            "BEGIN;\nfoo rollback\nCOMMIT;"
+        );
+    }
+
+    #[test]
+    fn empty_transactions() {
+        let report = Report {
+            problems: vec![Item {
+                kind: "foo",
+                message: "some foo".to_string(),
+                migration: None,
+                rollback: None,
+            }],
+            truncated: false,
+        };
+
+        assert_eq!(report.migration_transaction().unwrap(), "");
+        assert_eq!(report.rollback_transaction().unwrap(), "");
+    }
+
+    #[test]
+    fn contains() {
+        let report = Report {
+            problems: vec![Item {
+                kind: "foo",
+                message: "some foo".to_string(),
+                migration: None,
+                rollback: None,
+            }],
+            truncated: false,
+        };
+
+        assert!(report.contains("foo"));
+        assert!(!report.contains("bar"));
+    }
+
+    #[test]
+    fn counters() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "some foo".to_string(),
+                    migration: Some("foo migration".to_string()),
+                    rollback: Some("foo rollback".to_string()),
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "baz",
+                    message: "some baz".to_string(),
+                    migration: Some("baz migration".to_string()),
+                    rollback: None,
+                },
+                Item {
+                    kind: "qux",
+                    message: "some qux".to_string(),
+                    migration: Some("qux migration".to_string()),
+                    rollback: Some("qux rollback".to_string()),
+                },
+            ],
+            truncated: false,
+        };
+
+        assert!(!report.is_empty());
         assert_eq!(report.count(), 4);
         assert_eq!(report.count_migrations(), 3);
+        assert_eq!(report.count_rollbacks(), 2);
+    }
+
+    #[test]
+    fn severities() {
+        let report = Report {
+            problems: vec![
+                Rated {
+                    kind: "foo",
+                    severity: Severity::Warning,
+                },
+                Rated {
+                    kind: "bar",
+                    severity: Severity::Info,
+                },
+                Rated {
+                    kind: "baz",
+                    severity: Severity::Warning,
+                },
+            ],
+            truncated: false,
+        };
+
+        assert_eq!(
+            report.count_by_severity().into_iter().collect::<Vec<_>>(),
+            vec![(Severity::Info, 1), (Severity::Warning, 2)]
+        );
+        assert_eq!(report.max_severity(), Some(Severity::Warning));
+        assert_eq!(Report::<Rated>::default().max_severity(), None);
+    }
+
+    #[test]
+    fn default_severity() {
+        let report = Report {
+            problems: vec![Item {
+                kind: "foo",
+                message: "some foo".to_string(),
+                migration: None,
+                rollback: None,
+            }],
+            truncated: false,
+        };
+
+        assert_eq!(report.max_severity(), Some(Severity::Error));
+    }
+
+    #[test]
+    fn summary() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "first foo".to_string(),
+                    migration: Some("foo migration".to_string()),
+                    rollback: Some("foo rollback".to_string()),
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: Some("bar migration".to_string()),
+                    rollback: None,
+                },
+                Item {
+                    kind: "foo",
+                    message: "second foo".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        let summary = report.summary();
+
+        assert_eq!(
+            summary,
+            Summary {
+                total: 3,
+                migrations: 2,
+                rollbacks: 1,
+                by_kind: BTreeMap::from([("bar", 1), ("foo", 2)]),
+            }
+        );
+        assert_eq!(
+            serde_json::to_value(&summary).unwrap(),
+            serde_json::json!({
+                "total": 3,
+                "migrations": 2,
+                "rollbacks": 1,
+                "by_kind": { "bar": 1, "foo": 2 },
+            })
+        );
+    }
+
+    #[test]
+    fn count_by_kind() {
+        let report = Report {
+            problems: vec![
+                Item {
+                    kind: "foo",
+                    message: "first foo".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "bar",
+                    message: "some bar".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+                Item {
+                    kind: "foo",
+                    message: "second foo".to_string(),
+                    migration: None,
+                    rollback: None,
+                },
+            ],
+            truncated: false,
+        };
+
+        let counts = report.count_by_kind();
+
+        assert_eq!(
+            counts.into_iter().collect::<Vec<_>>(),
+            vec![("bar", 1), ("foo", 2)]
+        );
     }
 }
@@ -0,0 +1,435 @@
+use std::fs::{create_dir_all, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::{Error, Result};
+use crate::problem::Problem;
+use crate::report::Report;
+
+impl<P: Problem> Report<P> {
+    /// Write every migration into a separate `YYYYMMDDHHMMSS_<kind>.sql` file
+    /// in the directory (created if missed), and return paths to the files.
+    ///
+    /// The timestamp starts from the current UTC time, and is incremented by a second
+    /// for every next file to keep the names unique and ordered like the migrations are.
+    /// Existing files are never overwritten: the timestamp is incremented further
+    /// until the name is free (for example, when the previous run was a second ago),
+    /// so that timestamps of a large report can run ahead of the current time.
+    /// Characters of the kind other than ASCII letters, digits and `_` are replaced
+    /// by `_` to keep the name safe for the filesystem and migration tools.
+    pub fn write_migrations(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        self.write_migrations_since(dir, now())
+    }
+
+    fn write_migrations_since(&self, dir: &Path, since: u64) -> Result<Vec<PathBuf>> {
+        create_dir(dir)?;
+        let mut paths = vec![];
+        let mut next = since;
+        for problem in self.iter() {
+            if let Some(migration) = problem.migration() {
+                let migration = migration?;
+                let name = free_name(dir, &mut next, problem.kind(), &[".sql"]);
+                let path = dir.join(format!("{}.sql", name));
+                write_file(&path, &migration)?;
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Write every migration into a separate `YYYYMMDDHHMMSS_<kind>.up.sql` file,
+    /// along with its rollback (if any) in the `YYYYMMDDHHMMSS_<kind>.down.sql` one.
+    /// Timestamps are assigned in the same way as by `write_migrations`.
+    pub fn write_migrations_with_rollback(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        self.write_migrations_with_rollback_since(dir, now())
+    }
+
+    fn write_migrations_with_rollback_since(&self, dir: &Path, since: u64) -> Result<Vec<PathBuf>> {
+        create_dir(dir)?;
+        let mut paths = vec![];
+        let mut next = since;
+        for problem in self.iter() {
+            if let Some(migration) = problem.migration() {
+                let migration = migration?;
+                let rollback = problem.rollback().transpose()?;
+                let suffixes = [".up.sql", ".down.sql"];
+                let name = free_name(dir, &mut next, problem.kind(), &suffixes);
+
+                let path = dir.join(format!("{}.up.sql", name));
+                write_file(&path, &migration)?;
+                paths.push(path);
+
+                if let Some(rollback) = rollback {
+                    let path = dir.join(format!("{}.down.sql", name));
+                    write_file(&path, &rollback)?;
+                    paths.push(path);
+                }
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Write the combined migration into the Flyway versioned `V<version>__dblinter.sql` file,
+    /// and the combined rollback into the undo `U<version>__dblinter.sql` one.
+    ///
+    /// The undo file is written only when every migration has a rollback,
+    /// because a partial undo would leave the schema in an inconsistent state
+    /// (rollbacks of problems without migrations are ignored).
+    /// Nothing is written when there are no migrations at all,
+    /// and the version is rejected before writing anything when any of its files exists.
+    pub fn write_flyway(&self, dir: &Path, version: &str) -> Result<Vec<PathBuf>> {
+        if self.count_migrations() == 0 {
+            return Ok(vec![]);
+        }
+        let mut files = vec![(
+            dir.join(format!("V{}__dblinter.sql", version)),
+            self.migration()?,
+        )];
+        if let Some(undo) = self.undo()? {
+            files.push((dir.join(format!("U{}__dblinter.sql", version)), undo));
+        }
+        if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+            let err = io::Error::from(io::ErrorKind::AlreadyExists);
+            return Err(Error::WriteFile(path.clone(), err));
+        }
+
+        create_dir(dir)?;
+        let mut paths = vec![];
+        for (path, content) in files {
+            write_file(&path, &content)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    // Rollbacks of the migrations in the reverse order,
+    // or nothing when some migration cannot be rolled back.
+    fn undo(&self) -> Result<Option<String>> {
+        let mut rollbacks = vec![];
+        for problem in self.problems.iter().rev() {
+            if problem.migration().is_some() {
+                match problem.rollback() {
+                    Some(rollback) => rollbacks.push(rollback?),
+                    None => return Ok(None),
+                }
+            }
+        }
+        Ok(Some(rollbacks.join("\n")))
+    }
+}
+
+fn create_dir(dir: &Path) -> Result<()> {
+    create_dir_all(dir).map_err(|err| Error::WriteFile(dir.to_path_buf(), err))
+}
+
+// Write the new file (existing files are never overwritten)
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{}", content))
+        .map_err(|err| Error::WriteFile(path.to_path_buf(), err))
+}
+
+// The `YYYYMMDDHHMMSS_<kind>` name of the migration whose files (with the suffixes)
+// don't exist yet. The timestamp starts from the `next` one, which is then
+// moved to the second after the chosen timestamp.
+fn free_name(dir: &Path, next: &mut u64, kind: &str, suffixes: &[&str]) -> String {
+    let kind: String = kind
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    loop {
+        let name = format!("{}_{}", format_timestamp(*next), kind);
+        *next += 1;
+        if suffixes
+            .iter()
+            .all(|suffix| !dir.join(format!("{}{}", name, suffix)).exists())
+        {
+            return name;
+        }
+    }
+}
+
+// The number of seconds since the UNIX epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+// Format seconds since the UNIX epoch as `YYYYMMDDHHMMSS` in UTC.
+// The date is calculated by the civil-from-days algorithm by Howard Hinnant.
+fn format_timestamp(secs: u64) -> String {
+    let (days, secs) = (secs / 86400, secs % 86400);
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    format!(
+        "{:04}{:02}{:02}{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    )
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod test {
+    use super::*;
+    use crate::report::test::Item;
+    use std::fs::read_to_string;
+
+    fn report() -> Report<Item> {
+        Report::from_iter(vec![
+            Item {
+                kind: "foo",
+                message: "some foo".to_string(),
+                migration: Some("foo migration".to_string()),
+                rollback: Some("foo rollback".to_string()),
+            },
+            Item {
+                kind: "bar",
+                message: "some bar".to_string(),
+                migration: None,
+                rollback: None,
+            },
+            Item {
+                kind: "foo",
+                message: "another foo".to_string(),
+                migration: Some("another foo migration".to_string()),
+                rollback: None,
+            },
+        ])
+    }
+
+    #[test]
+    fn timestamp() {
+        assert_eq!(format_timestamp(0), "19700101000000");
+        assert_eq!(format_timestamp(951782400), "20000229000000");
+        assert_eq!(format_timestamp(1700000000), "20231114221320");
+    }
+
+    #[test]
+    fn write_migrations() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().join("migrations");
+
+        let paths = report().write_migrations_since(&dir, 1700000000).unwrap();
+
+        assert_eq!(
+            paths,
+            vec![
+                dir.join("20231114221320_foo.sql"),
+                dir.join("20231114221321_foo.sql"),
+            ]
+        );
+        assert_eq!(read_to_string(&paths[0]).unwrap(), "foo migration\n");
+        assert_eq!(
+            read_to_string(&paths[1]).unwrap(),
+            "another foo migration\n"
+        );
+    }
+
+    #[test]
+    fn write_migrations_with_unsafe_kind() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let report = Report::from_iter(vec![Item {
+            kind: "../foo bar",
+            message: "some foo".to_string(),
+            migration: Some("foo migration".to_string()),
+            rollback: None,
+        }]);
+
+        let paths = report.write_migrations_since(dir, 1700000000).unwrap();
+
+        assert_eq!(paths, vec![dir.join("20231114221320____foo_bar.sql")]);
+    }
+
+    #[test]
+    fn write_migrations_twice() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        report().write_migrations_since(dir, 1700000000).unwrap();
+
+        let paths = report().write_migrations_since(dir, 1700000001).unwrap();
+
+        assert_eq!(
+            paths,
+            vec![
+                dir.join("20231114221322_foo.sql"),
+                dir.join("20231114221323_foo.sql"),
+            ]
+        );
+        assert_eq!(
+            read_to_string(dir.join("20231114221321_foo.sql")).unwrap(),
+            "another foo migration\n"
+        );
+    }
+
+    #[test]
+    fn write_migrations_with_rollback_twice() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        report()
+            .write_migrations_with_rollback_since(dir, 1700000000)
+            .unwrap();
+
+        let paths = report()
+            .write_migrations_with_rollback_since(dir, 1700000000)
+            .unwrap();
+
+        assert_eq!(
+            paths,
+            vec![
+                dir.join("20231114221322_foo.up.sql"),
+                dir.join("20231114221322_foo.down.sql"),
+                dir.join("20231114221323_foo.up.sql"),
+            ]
+        );
+    }
+
+    #[test]
+    fn write_migrations_with_rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        let paths = report()
+            .write_migrations_with_rollback_since(dir, 1700000000)
+            .unwrap();
+
+        assert_eq!(
+            paths,
+            vec![
+                dir.join("20231114221320_foo.up.sql"),
+                dir.join("20231114221320_foo.down.sql"),
+                dir.join("20231114221321_foo.up.sql"),
+            ]
+        );
+        assert_eq!(read_to_string(&paths[0]).unwrap(), "foo migration\n");
+        assert_eq!(read_to_string(&paths[1]).unwrap(), "foo rollback\n");
+        assert_eq!(
+            read_to_string(&paths[2]).unwrap(),
+            "another foo migration\n"
+        );
+        assert!(!dir.join("20231114221321_foo.down.sql").exists());
+    }
+
+    #[test]
+    fn write_flyway_without_undo() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        let paths = report().write_flyway(dir, "1.2").unwrap();
+
+        assert_eq!(paths, vec![dir.join("V1.2__dblinter.sql")]);
+        assert_eq!(
+            read_to_string(&paths[0]).unwrap(),
+            "foo migration\nanother foo migration\n"
+        );
+    }
+
+    #[test]
+    fn write_flyway_with_undo() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let mut report = report();
+        report.retain(|p| p.rollback.is_some() || p.migration.is_none());
+
+        let paths = report.write_flyway(dir, "3").unwrap();
+
+        assert_eq!(
+            paths,
+            vec![dir.join("V3__dblinter.sql"), dir.join("U3__dblinter.sql")]
+        );
+        assert_eq!(read_to_string(&paths[0]).unwrap(), "foo migration\n");
+        assert_eq!(read_to_string(&paths[1]).unwrap(), "foo rollback\n");
+    }
+
+    #[test]
+    fn write_flyway_existing_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        report().write_flyway(dir, "1").unwrap();
+
+        let error = report().write_flyway(dir, "1").unwrap_err();
+
+        assert!(matches!(error, Error::WriteFile(path, _) if path == dir.join("V1__dblinter.sql")));
+        assert_eq!(
+            read_to_string(dir.join("V1__dblinter.sql")).unwrap(),
+            "foo migration\nanother foo migration\n"
+        );
+    }
+
+    #[test]
+    fn write_flyway_existing_undo() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let mut report = report();
+        report.retain(|p| p.rollback.is_some());
+        write_file(&dir.join("U1__dblinter.sql"), "").unwrap();
+
+        let error = report.write_flyway(dir, "1").unwrap_err();
+
+        assert!(matches!(error, Error::WriteFile(path, _) if path == dir.join("U1__dblinter.sql")));
+        assert!(!dir.join("V1__dblinter.sql").exists());
+    }
+
+    #[test]
+    fn write_flyway_ignoring_rollbacks_without_migrations() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let report = Report::from_iter(vec![
+            Item {
+                kind: "foo",
+                message: "some foo".to_string(),
+                migration: Some("foo migration".to_string()),
+                rollback: Some("foo rollback".to_string()),
+            },
+            Item {
+                kind: "bar",
+                message: "some bar".to_string(),
+                migration: None,
+                rollback: Some("bar rollback".to_string()),
+            },
+        ]);
+
+        let paths = report.write_flyway(dir, "1").unwrap();
+
+        assert_eq!(read_to_string(&paths[1]).unwrap(), "foo rollback\n");
+    }
+
+    #[test]
+    fn write_flyway_without_migrations() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut report = report();
+        report.retain(|p| p.migration.is_none());
+
+        let paths = report.write_flyway(dir.path(), "1").unwrap();
+
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    fn write_migrations_now() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let paths = report().write_migrations(dir.path()).unwrap();
+
+        assert_eq!(paths.len(), 2);
+        assert_ne!(paths[0], paths[1]);
+        assert!(paths.iter().all(|p| p.exists()));
+    }
+}
@@ -0,0 +1,559 @@
+use regex::{Captures, Regex};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use crate::error::Result;
+use crate::problem::Problem;
+use crate::report::Report;
+
+impl<P: Problem> Report<P> {
+    /// The migration rewritten to be safely re-applied after a partial failure.
+    ///
+    /// The following statement patterns are supported (case-insensitive):
+    ///
+    /// * `CREATE TABLE` -> `CREATE TABLE IF NOT EXISTS`
+    /// * `CREATE [UNIQUE] INDEX [CONCURRENTLY] name` -> `... INDEX [CONCURRENTLY] IF NOT EXISTS name`
+    /// * `ADD COLUMN` -> `ADD COLUMN IF NOT EXISTS`
+    /// * `DROP {TABLE|INDEX [CONCURRENTLY]|COLUMN|CONSTRAINT}` -> `DROP ... IF EXISTS`
+    ///
+    /// Other statements are kept as is, including `ADD CONSTRAINT` and unnamed
+    /// `CREATE INDEX ON ...`, for which PostgreSQL has no `IF NOT EXISTS` clause.
+    /// String literals (including dollar-quoted ones) and comments are not rewritten.
+    pub fn migration_idempotent(&self) -> Result<String> {
+        self.migration().map(|sql| idempotent(&sql))
+    }
+
+    /// The rollback rewritten in the same way as `migration_idempotent`.
+    pub fn rollback_idempotent(&self) -> Result<String> {
+        self.rollback().map(|sql| idempotent(&sql))
+    }
+
+    /// The migration where every statement is preceded by the SQL comment
+    /// with the kind and the message of the problem fixed by the statement.
+    pub fn migration_annotated(&self) -> Result<String> {
+        let mut output = String::new();
+        for problem in self.iter() {
+            if let Some(migration) = problem.migration() {
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+                let header = format!("{}: {}", problem.kind(), problem.message()?);
+                for line in header.lines() {
+                    output.push_str(&format!("-- {}\n", line));
+                }
+                output.push_str(migration?.as_str());
+            }
+        }
+        Ok(output)
+    }
+
+    /// Migrations grouped by the (schema, table) target of their problems.
+    /// Migrations of problems without a target are collected under the ("", "") key.
+    pub fn migrations_by_table(&self) -> Result<BTreeMap<(String, String), String>> {
+        let mut output: BTreeMap<(String, String), String> = BTreeMap::new();
+        for problem in self.iter() {
+            if let Some(migration) = problem.migration() {
+                let group = output
+                    .entry(problem.target().unwrap_or_default())
+                    .or_default();
+                if !group.is_empty() {
+                    group.push('\n');
+                }
+                group.push_str(migration?.as_str());
+            }
+        }
+        Ok(output)
+    }
+
+    /// The migration where statements are sorted so that migrations of problems
+    /// listed in `depends_on` of some problem go before the migration of that problem.
+    /// Independent migrations keep the order in which the problems were discovered.
+    ///
+    /// When dependencies are cyclic, all migrations are kept in the discovery order,
+    /// and the output is preceded by the SQL comment with a warning.
+    pub fn migration_ordered(&self) -> Result<String> {
+        let problems: Vec<&P> = self.iter().filter(|p| p.migration().is_some()).collect();
+        let mut output = String::new();
+        let order = dependency_order(&problems).unwrap_or_else(|| {
+            output.push_str("-- WARNING: cyclic dependencies between problems, ");
+            output.push_str("the migrations are kept in the discovery order\n");
+            (0..problems.len()).collect()
+        });
+        let mut first = true;
+        for index in order {
+            if let Some(migration) = problems[index].migration() {
+                if !first {
+                    output.push('\n');
+                }
+                first = false;
+                output.push_str(migration?.as_str());
+            }
+        }
+        Ok(output)
+    }
+
+    /// The migration file in the dbmate format with the migration
+    /// under the `-- migrate:up` marker, and the (reversed) rollback
+    /// under the `-- migrate:down` one.
+    pub fn to_dbmate(&self) -> Result<String> {
+        Ok(format!(
+            "-- migrate:up\n{}\n\n-- migrate:down\n{}\n",
+            self.migration()?,
+            self.rollback()?,
+        ))
+    }
+}
+
+// Sort indexes of problems topologically so that dependencies go first,
+// preferring the earliest discovered problem among the available ones.
+// Return `None` if dependencies are cyclic.
+fn dependency_order<P: Problem>(problems: &[&P]) -> Option<Vec<usize>> {
+    let dependencies: Vec<Vec<usize>> = problems
+        .iter()
+        .enumerate()
+        .map(|(i, problem)| {
+            let kinds = problem.depends_on();
+            (0..problems.len())
+                .filter(|&j| j != i && kinds.iter().any(|k| k == problems[j].kind()))
+                .collect()
+        })
+        .collect();
+
+    let mut done = vec![false; problems.len()];
+    let mut order = Vec::with_capacity(problems.len());
+    while order.len() < problems.len() {
+        let next =
+            (0..problems.len()).find(|&i| !done[i] && dependencies[i].iter().all(|&j| done[j]))?;
+        done[next] = true;
+        order.push(next);
+    }
+    Some(order)
+}
+
+// Add `IF [NOT] EXISTS` clauses to the supported statements
+// without touching string literals and comments.
+fn idempotent(sql: &str) -> String {
+    split(sql)
+        .into_iter()
+        .map(|(code, part)| {
+            if code {
+                rules()
+                    .iter()
+                    .fold(part.to_string(), |output, (re, rewrite)| {
+                        re.replace_all(&output, *rewrite).to_string()
+                    })
+            } else {
+                part.to_string()
+            }
+        })
+        .collect()
+}
+
+type Rewrite = fn(&Captures) -> String;
+
+// Statements with `IF [NOT] EXISTS` clauses supported by PostgreSQL
+fn rules() -> &'static [(Regex, Rewrite)] {
+    static RULES: OnceLock<Vec<(Regex, Rewrite)>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let rules: [(&str, Rewrite); 4] = [
+            (r"(?i)\bCREATE\s+TABLE\s+(IF\s+NOT\s+EXISTS\s+)?", |_| {
+                "CREATE TABLE IF NOT EXISTS ".to_string()
+            }),
+            (
+                r#"(?i)\bCREATE\s+(UNIQUE\s+)?INDEX\s+(CONCURRENTLY\s+)?(IF\s+NOT\s+EXISTS\s+)?("[^"]+"|\w+)"#,
+                // The clause requires the name of the index (`CREATE INDEX ON ...` is kept as is)
+                |caps| {
+                    if caps[4].eq_ignore_ascii_case("ON") {
+                        caps[0].to_string()
+                    } else {
+                        format!(
+                            "CREATE {}INDEX {}IF NOT EXISTS {}",
+                            caps.get(1).map_or("", |m| m.as_str()).to_uppercase(),
+                            caps.get(2).map_or("", |m| m.as_str()).to_uppercase(),
+                            &caps[4],
+                        )
+                    }
+                },
+            ),
+            (r"(?i)\bADD\s+COLUMN\s+(IF\s+NOT\s+EXISTS\s+)?", |_| {
+                "ADD COLUMN IF NOT EXISTS ".to_string()
+            }),
+            (
+                r"(?i)\bDROP\s+(TABLE|INDEX|COLUMN|CONSTRAINT)\s+(CONCURRENTLY\s+)?(IF\s+EXISTS\s+)?",
+                |caps| {
+                    format!(
+                        "DROP {} {}IF EXISTS ",
+                        caps[1].to_uppercase(),
+                        caps.get(2).map_or("", |m| m.as_str()).to_uppercase(),
+                    )
+                },
+            ),
+        ];
+        rules
+            .into_iter()
+            .map(|(pattern, rewrite)| (Regex::new(pattern).unwrap(), rewrite))
+            .collect()
+    })
+}
+
+// Split SQL into parts of code (`true`), and string literals or comments (`false`).
+// Quoted identifiers are kept in the code, because they can follow the keywords.
+fn split(sql: &str) -> Vec<(bool, &str)> {
+    static DOLLAR: OnceLock<Regex> = OnceLock::new();
+    let dollar = DOLLAR.get_or_init(|| Regex::new(r"^\$([A-Za-z_]\w*)?\$").unwrap());
+
+    let mut parts = vec![];
+    let (mut start, mut pos) = (0, 0);
+    while let Some(first) = sql[pos..].chars().next() {
+        let rest = &sql[pos..];
+        let close = |from: usize, tag: &str| rest[from..].find(tag).map(|i| pos + from + i);
+        let end = if rest.starts_with("--") {
+            close(2, "\n").unwrap_or(sql.len())
+        } else if rest.starts_with("/*") {
+            close(2, "*/").map_or(sql.len(), |i| i + 2)
+        } else if let Some(tag) = dollar.find(rest).map(|m| m.as_str()) {
+            close(tag.len(), tag).map_or(sql.len(), |i| i + tag.len())
+        } else if first == '\'' {
+            // The quote is escaped by doubling it: 'it''s'
+            let mut from = 1;
+            loop {
+                match close(from, "'") {
+                    Some(i) if sql[i + 1..].starts_with('\'') => from = i + 2 - pos,
+                    Some(i) => break i + 1,
+                    None => break sql.len(),
+                }
+            }
+        } else {
+            pos = match first {
+                '"' => close(1, "\"").map_or(sql.len(), |i| i + 1),
+                _ => pos + first.len_utf8(),
+            };
+            continue;
+        };
+        if start < pos {
+            parts.push((true, &sql[start..pos]));
+        }
+        parts.push((false, &sql[pos..end]));
+        (start, pos) = (end, end);
+    }
+    if start < sql.len() {
+        parts.push((true, &sql[start..]));
+    }
+    parts
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod test {
+    use super::*;
+    use crate::client::PostgresClient;
+    use crate::report::test::Item;
+
+    // The problem bound to some table
+    struct Table {
+        schema: &'static str,
+        table: &'static str,
+        migration: &'static str,
+    }
+    impl Problem for Table {
+        type Client = PostgresClient;
+
+        fn kind(&self) -> &'static str {
+            "table"
+        }
+        fn message(&self) -> Result<String> {
+            Ok(format!("{}.{}", self.schema, self.table))
+        }
+        fn migration(&self) -> Option<Result<String>> {
+            Some(Ok(self.migration.to_string()))
+        }
+        fn rollback(&self) -> Option<Result<String>> {
+            None
+        }
+        fn target(&self) -> Option<(String, String)> {
+            Some((self.schema.to_string(), self.table.to_string()))
+        }
+    }
+
+    #[test]
+    fn idempotent_statements() {
+        let cases = [
+            (
+                "CREATE TABLE foo (id int);",
+                "CREATE TABLE IF NOT EXISTS foo (id int);",
+            ),
+            (
+                "create table if not exists foo (id int);",
+                "CREATE TABLE IF NOT EXISTS foo (id int);",
+            ),
+            (
+                "CREATE UNIQUE INDEX CONCURRENTLY foo_idx ON foo (id);",
+                "CREATE UNIQUE INDEX CONCURRENTLY IF NOT EXISTS foo_idx ON foo (id);",
+            ),
+            (
+                "CREATE INDEX foo_idx ON foo (id);",
+                "CREATE INDEX IF NOT EXISTS foo_idx ON foo (id);",
+            ),
+            (
+                "ALTER TABLE foo ADD COLUMN bar int;",
+                "ALTER TABLE foo ADD COLUMN IF NOT EXISTS bar int;",
+            ),
+            (
+                "ALTER TABLE foo ADD CONSTRAINT foo_bar_limit CHECK (bar < 10);",
+                "ALTER TABLE foo ADD CONSTRAINT foo_bar_limit CHECK (bar < 10);",
+            ),
+            (
+                "CREATE INDEX ON foo (id);",
+                "CREATE INDEX ON foo (id);",
+            ),
+            (
+                "create index concurrently on foo (id);",
+                "create index concurrently on foo (id);",
+            ),
+            (
+                "CREATE INDEX \"Foo idx\" ON foo (id);",
+                "CREATE INDEX IF NOT EXISTS \"Foo idx\" ON foo (id);",
+            ),
+            (
+                "INSERT INTO log VALUES ('it''s CREATE TABLE foo'); -- drop table bar\nDROP TABLE baz;",
+                "INSERT INTO log VALUES ('it''s CREATE TABLE foo'); -- drop table bar\nDROP TABLE IF EXISTS baz;",
+            ),
+            (
+                "/* create index foo_idx on foo */ COMMENT ON TABLE foo IS $$drop table foo$$;",
+                "/* create index foo_idx on foo */ COMMENT ON TABLE foo IS $$drop table foo$$;",
+            ),
+            (
+                "ALTER TABLE foo DROP CONSTRAINT foo_bar_limit;",
+                "ALTER TABLE foo DROP CONSTRAINT IF EXISTS foo_bar_limit;",
+            ),
+            (
+                "DROP INDEX CONCURRENTLY if exists foo_idx;",
+                "DROP INDEX CONCURRENTLY IF EXISTS foo_idx;",
+            ),
+            ("UPDATE foo SET bar = 1;", "UPDATE foo SET bar = 1;"),
+        ];
+
+        for (input, output) in cases {
+            assert_eq!(idempotent(input), output);
+        }
+    }
+
+    #[test]
+    fn migration_annotated() {
+        let report = Report::from_iter(vec![
+            Item {
+                kind: "foo",
+                message: "some foo".to_string(),
+                migration: Some("foo migration".to_string()),
+                rollback: None,
+            },
+            Item {
+                kind: "bar",
+                message: "some bar".to_string(),
+                migration: None,
+                rollback: None,
+            },
+            Item {
+                kind: "baz",
+                message: "some baz\nin two lines".to_string(),
+                migration: Some("baz migration".to_string()),
+                rollback: None,
+            },
+        ]);
+
+        assert_eq!(
+            report.migration_annotated().unwrap(),
+            "-- foo: some foo\n\
+             foo migration\n\
+             -- baz: some baz\n\
+             -- in two lines\n\
+             baz migration"
+        );
+        assert_eq!(report.migration().unwrap(), "foo migration\nbaz migration");
+    }
+
+    #[test]
+    fn migrations_by_table() {
+        let report = Report::from_iter(vec![
+            Table {
+                schema: "public",
+                table: "users",
+                migration: "users migration",
+            },
+            Table {
+                schema: "admin",
+                table: "roles",
+                migration: "roles migration",
+            },
+            Table {
+                schema: "public",
+                table: "users",
+                migration: "another users migration",
+            },
+        ]);
+
+        let groups = report.migrations_by_table().unwrap();
+
+        assert_eq!(
+            groups.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    ("admin".to_string(), "roles".to_string()),
+                    "roles migration".to_string()
+                ),
+                (
+                    ("public".to_string(), "users".to_string()),
+                    "users migration\nanother users migration".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn ungrouped_migrations() {
+        let report = Report::from_iter(vec![
+            Item {
+                kind: "foo",
+                message: "some foo".to_string(),
+                migration: Some("foo migration".to_string()),
+                rollback: None,
+            },
+            Item {
+                kind: "bar",
+                message: "some bar".to_string(),
+                migration: None,
+                rollback: None,
+            },
+        ]);
+
+        let groups = report.migrations_by_table().unwrap();
+
+        assert_eq!(
+            groups.into_iter().collect::<Vec<_>>(),
+            vec![((String::new(), String::new()), "foo migration".to_string())]
+        );
+    }
+
+    // The problem which depends on problems of other kinds
+    struct Dependent {
+        kind: &'static str,
+        depends_on: &'static [&'static str],
+    }
+    impl Problem for Dependent {
+        type Client = PostgresClient;
+
+        fn kind(&self) -> &'static str {
+            self.kind
+        }
+        fn message(&self) -> Result<String> {
+            Ok(self.kind.to_string())
+        }
+        fn migration(&self) -> Option<Result<String>> {
+            Some(Ok(format!("{} migration", self.kind)))
+        }
+        fn rollback(&self) -> Option<Result<String>> {
+            None
+        }
+        fn depends_on(&self) -> Vec<String> {
+            self.depends_on.iter().map(|k| k.to_string()).collect()
+        }
+    }
+
+    #[test]
+    fn migration_ordered() {
+        let report = Report::from_iter(vec![
+            Dependent {
+                kind: "constraint",
+                depends_on: &["table"],
+            },
+            Dependent {
+                kind: "index",
+                depends_on: &[],
+            },
+            Dependent {
+                kind: "table",
+                depends_on: &[],
+            },
+        ]);
+
+        assert_eq!(
+            report.migration_ordered().unwrap(),
+            "index migration\ntable migration\nconstraint migration"
+        );
+    }
+
+    #[test]
+    fn migration_ordered_with_cycle() {
+        let report = Report::from_iter(vec![
+            Dependent {
+                kind: "foo",
+                depends_on: &["bar"],
+            },
+            Dependent {
+                kind: "bar",
+                depends_on: &["foo"],
+            },
+        ]);
+
+        assert_eq!(
+            report.migration_ordered().unwrap(),
+            "-- WARNING: cyclic dependencies between problems, \
+             the migrations are kept in the discovery order\n\
+             foo migration\n\
+             bar migration"
+        );
+    }
+
+    #[test]
+    fn to_dbmate() {
+        let report = Report::from_iter(vec![
+            Item {
+                kind: "foo",
+                message: "some foo".to_string(),
+                migration: Some("foo migration".to_string()),
+                rollback: Some("foo rollback".to_string()),
+            },
+            Item {
+                kind: "bar",
+                message: "some bar".to_string(),
+                migration: None,
+                rollback: None,
+            },
+            Item {
+                kind: "baz",
+                message: "some baz".to_string(),
+                migration: Some("baz migration".to_string()),
+                rollback: Some("baz rollback".to_string()),
+            },
+        ]);
+
+        assert_eq!(
+            report.to_dbmate().unwrap(),
+            "-- migrate:up\n\
+             foo migration\n\
+             baz migration\n\
+             \n\
+             -- migrate:down\n\
+             baz rollback\n\
+             foo rollback\n"
+        );
+    }
+
+    #[test]
+    fn migration_idempotent() {
+        let report = Report::from_iter(vec![Item {
+            kind: "foo",
+            message: "some foo".to_string(),
+            migration: Some("ALTER TABLE foo ADD CONSTRAINT foo_limit CHECK (x < 1);".to_string()),
+            rollback: Some("ALTER TABLE foo DROP CONSTRAINT foo_limit;".to_string()),
+        }]);
+
+        assert_eq!(
+            report.migration_idempotent().unwrap(),
+            "ALTER TABLE foo ADD CONSTRAINT foo_limit CHECK (x < 1);"
+        );
+        assert_eq!(
+            report.rollback_idempotent().unwrap(),
+            "ALTER TABLE foo DROP CONSTRAINT IF EXISTS foo_limit;"
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// The severity of a problem ordered from the least to the most severe one.
+#[repr(C)]
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    #[default]
+    Error,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn order() {
+        assert!(Severity::Info < Severity::Warning);
+        assert!(Severity::Warning < Severity::Error);
+        assert_eq!(Severity::default(), Severity::Error);
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            serde_json::to_string(&[Severity::Info, Severity::Warning, Severity::Error]).unwrap(),
+            r#"["info","warning","error"]"#
+        );
+    }
+}
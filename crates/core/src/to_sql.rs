@@ -1,3 +1,4 @@
+use macros_core::OPERATORS;
 use regex::Regex;
 use serde::{ser, Serialize};
 use std::error::Error as StdError;
@@ -62,7 +63,7 @@ impl NameSerializer {
     }
 }
 
-impl<'a> ser::Serializer for &'a mut NameSerializer {
+impl ser::Serializer for &mut NameSerializer {
     type Ok = String;
     type Error = Error;
 
@@ -267,7 +268,7 @@ impl ValueSerializer {
     }
 }
 
-impl<'a> ser::Serializer for &'a mut ValueSerializer {
+impl ser::Serializer for &mut ValueSerializer {
     type Ok = ();
     type Error = Error;
 
@@ -461,7 +462,7 @@ impl<'a> ser::Serializer for &'a mut ValueSerializer {
     }
 }
 
-impl<'a> ser::SerializeSeq for &'a mut ValueSerializer {
+impl ser::SerializeSeq for &mut ValueSerializer {
     type Ok = ();
     type Error = Error;
 
@@ -495,7 +496,7 @@ impl FilterItemSerializer {
     }
 }
 
-impl<'a> ser::Serializer for &'a mut FilterItemSerializer {
+impl ser::Serializer for &mut FilterItemSerializer {
     type Ok = ();
     type Error = Error;
 
@@ -673,7 +674,7 @@ impl<'a> ser::Serializer for &'a mut FilterItemSerializer {
     }
 }
 
-impl<'a> ser::SerializeStruct for &'a mut FilterItemSerializer {
+impl ser::SerializeStruct for &mut FilterItemSerializer {
     type Ok = ();
     type Error = Error;
 
@@ -693,10 +694,18 @@ impl<'a> ser::SerializeStruct for &'a mut FilterItemSerializer {
             self.output += " AND ";
         }
 
+        // The operator follows the name of the column separated by a space (like `"size >="`)
+        let (key, op) = key.split_once(' ').unwrap_or((key, "="));
+        if !OPERATORS.contains(&op) {
+            return Err(Error::Filter(format!("operator {:?}", op)));
+        }
+
         let mut name_serializer = NameSerializer::new();
         key.serialize(&mut name_serializer)?;
         self.output += &name_serializer.output;
-        self.output += " = ";
+        self.output += " ";
+        self.output += op;
+        self.output += " ";
         self.output += value;
 
         Ok(())
@@ -720,7 +729,7 @@ impl FilterListSerializer {
     }
 }
 
-impl<'a> ser::Serializer for &'a mut FilterListSerializer {
+impl ser::Serializer for &mut FilterListSerializer {
     type Ok = ();
     type Error = Error;
 
@@ -892,7 +901,7 @@ impl<'a> ser::Serializer for &'a mut FilterListSerializer {
     }
 }
 
-impl<'a> ser::SerializeSeq for &'a mut FilterListSerializer {
+impl ser::SerializeSeq for &mut FilterListSerializer {
     type Ok = ();
     type Error = Error;
 
@@ -934,7 +943,7 @@ impl WhereSerializer {
     }
 }
 
-impl<'a> ser::Serializer for &'a mut WhereSerializer {
+impl ser::Serializer for &mut WhereSerializer {
     type Ok = ();
     type Error = Error;
 
@@ -1106,7 +1115,7 @@ impl<'a> ser::Serializer for &'a mut WhereSerializer {
     }
 }
 
-impl<'a> ser::SerializeStruct for &'a mut WhereSerializer {
+impl ser::SerializeStruct for &mut WhereSerializer {
     type Ok = ();
     type Error = Error;
 
@@ -1228,4 +1237,60 @@ mod test {
 
         assert_eq!(sql, f.to_sql().unwrap());
     }
+
+    #[repr(C)]
+    #[derive(Serialize)]
+    struct MyOperatorItem {
+        #[serde(rename = "size >=")]
+        min_size: Option<i32>,
+        #[serde(rename = "table_name NOT LIKE")]
+        table_name: Option<String>,
+    }
+
+    #[repr(C)]
+    #[derive(Serialize)]
+    struct MyOperatorFilter {
+        only: Option<Vec<MyOperatorItem>>,
+    }
+
+    impl ToSql for MyOperatorFilter {}
+
+    #[test]
+    fn operators() {
+        let f = MyOperatorFilter {
+            only: Some(vec![MyOperatorItem {
+                min_size: Some(10),
+                table_name: Some("tmp_%".to_string()),
+            }]),
+        };
+
+        assert_eq!(
+            f.to_sql().unwrap(),
+            " WHERE size >= 10 AND table_name NOT LIKE 'tmp_%'"
+        );
+    }
+
+    #[repr(C)]
+    #[derive(Serialize)]
+    struct MyInvalidItem {
+        #[serde(rename = "size ; DROP")]
+        size: Option<i32>,
+    }
+
+    #[repr(C)]
+    #[derive(Serialize)]
+    struct MyInvalidFilter {
+        only: Option<Vec<MyInvalidItem>>,
+    }
+
+    impl ToSql for MyInvalidFilter {}
+
+    #[test]
+    fn unknown_operator() {
+        let f = MyInvalidFilter {
+            only: Some(vec![MyInvalidItem { size: Some(10) }]),
+        };
+
+        assert!(f.to_sql().is_err());
+    }
 }
@@ -46,6 +46,124 @@ mod custom {
         }
     }
 
+    #[derive(Debug, Deserialize, FromRow)]
+    pub struct TableMissed {
+        pub table_name: String,
+    }
+
+    impl From<&TableMissed> for Context {
+        fn from(value: &TableMissed) -> Self {
+            let mut context = Self::new();
+            context.insert("table_name", &value.table_name);
+            context
+        }
+    }
+    impl CustomProblem for TableMissed {
+        type Client = PostgresClient;
+
+        fn kind_() -> &'static str {
+            "TableMissed"
+        }
+        fn message_() -> &'static str {
+            "The table {{ table_name | shout }} is missed"
+        }
+        fn register_filters(tera: &mut Tera) {
+            tera.register_filter("shout", |value: &tera::Value, _: &_| {
+                Ok(value.as_str().unwrap_or_default().to_uppercase().into())
+            });
+        }
+    }
+
+    #[derive(Debug, Deserialize, FromRow)]
+    pub struct IndexMissed {
+        pub index_name: String,
+    }
+
+    impl From<&IndexMissed> for Context {
+        fn from(value: &IndexMissed) -> Self {
+            let mut context = Self::new();
+            context.insert("index_name", &value.index_name);
+            context
+        }
+    }
+    impl CustomProblem for IndexMissed {
+        type Client = PostgresClient;
+
+        fn kind_() -> &'static str {
+            "IndexMissed"
+        }
+        fn message_() -> &'static str {
+            "The index {{ index_name is missed"
+        }
+    }
+
+    #[derive(Debug, Deserialize, FromRow)]
+    pub struct TriggerLeft {
+        pub trigger_name: String,
+        pub table_name: String,
+    }
+
+    impl From<&TriggerLeft> for Context {
+        fn from(value: &TriggerLeft) -> Self {
+            let mut context = Self::new();
+            context.insert("trigger_name", &value.trigger_name);
+            context.insert("table_name", &value.table_name);
+            context
+        }
+    }
+    impl CustomProblem for TriggerLeft {
+        type Client = PostgresClient;
+
+        fn kind_() -> &'static str {
+            "TriggerLeft"
+        }
+        fn message_() -> &'static str {
+            "The temporary trigger {{ trigger_name }} is left on {{ table_name }}"
+        }
+        fn rollback_() -> Option<&'static str> {
+            Some("DROP TRIGGER {{ trigger_name }} ON {{ table_name }};")
+        }
+        fn severity_() -> Severity {
+            Severity::Warning
+        }
+    }
+
+    #[derive(Debug, Deserialize, FromRow)]
+    pub struct SchemaUndocumented {
+        pub schema_name: String,
+    }
+
+    impl From<&SchemaUndocumented> for Context {
+        fn from(value: &SchemaUndocumented) -> Self {
+            let mut context = Self::new();
+            context.insert("schema_name", &value.schema_name);
+            context
+        }
+    }
+    impl CustomProblem for SchemaUndocumented {
+        type Client = PostgresClient;
+
+        fn kind_() -> &'static str {
+            "SchemaUndocumented"
+        }
+        fn message_() -> &'static str {
+            "The schema {{ database }}.{{ schema_name }} has no comment"
+        }
+        fn message_locale(locale: &str) -> Option<&'static str> {
+            match locale {
+                "de" => Some("Das Schema {{ database }}.{{ schema_name }} hat keinen Kommentar"),
+                "fr" => Some("Le schéma {{ database }}.{{ schema_name }} n'a pas de commentaire"),
+                _ => None,
+            }
+        }
+        fn globals() -> Context {
+            let mut context = Context::new();
+            context.insert("database", "app");
+            context.insert("schema_name", "public");
+            context
+        }
+    }
+
     #[derive(Debug)]
     pub enum TestProblem {
         ColumnLimitMissed(ColumnLimitMissed),
@@ -132,6 +250,34 @@ mod custom {
     }
 }
 
+// The problem of the same kind as `custom::TableMissed` but with another template
+mod renamed {
+    use super::*;
+
+    #[derive(Debug, Deserialize, FromRow)]
+    pub struct TableMissed {
+        pub table_name: String,
+    }
+
+    impl From<&TableMissed> for Context {
+        fn from(value: &TableMissed) -> Self {
+            let mut context = Self::new();
+            context.insert("table_name", &value.table_name);
+            context
+        }
+    }
+    impl CustomProblem for TableMissed {
+        type Client = PostgresClient;
+
+        fn kind_() -> &'static str {
+            "TableMissed"
+        }
+        fn message_() -> &'static str {
+            "Add the table {{ table_name }}"
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct TestLinter {}
 
@@ -162,3 +308,208 @@ fn test_problem() {
         "ALTER TABLE public.users DROP CONSTRAINT users_email_limit;"
     );
 }
+
+#[test]
+fn dry_run() {
+    let config = r#"{
+        "ColumnLimitMissed": {
+            "limit": 40,
+            "only": [{"scope_name": "public", "table_name": "users"}],
+            "except": null
+        }
+    }"#;
+    let queries = TestLinter::dry_run(config).unwrap();
+
+    assert!(queries["ColumnLimitMissed"]
+        .ends_with("WHERE limit = 40 AND scope_name = 'public' AND table_name = 'users';"));
+}
+
+#[test]
+fn test_problems_of_the_same_kind() {
+    let users = custom::ColumnLimitMissed {
+        scope_name: "public".to_string(),
+        table_name: "users".to_string(),
+        column_name: "email".to_string(),
+        limit: 40,
+    };
+    let orders = custom::ColumnLimitMissed {
+        scope_name: "public".to_string(),
+        table_name: "orders".to_string(),
+        column_name: "comment".to_string(),
+        limit: 255,
+    };
+
+    assert_eq!(
+        users.message().unwrap(),
+        "The column public.users (email) is not limited to 40 chars"
+    );
+    assert_eq!(
+        orders.message().unwrap(),
+        "The column public.orders (comment) is not limited to 255 chars"
+    );
+}
+
+#[test]
+fn test_problem_types_of_the_same_kind() {
+    let custom = custom::TableMissed {
+        table_name: "users".to_string(),
+    };
+    let renamed = renamed::TableMissed {
+        table_name: "users".to_string(),
+    };
+
+    assert_eq!(custom.message().unwrap(), "The table USERS is missed");
+    assert_eq!(renamed.message().unwrap(), "Add the table users");
+    assert_eq!(custom.message().unwrap(), "The table USERS is missed");
+}
+
+#[test]
+fn test_problem_with_custom_filter() {
+    let problem = custom::TableMissed {
+        table_name: "users".to_string(),
+    };
+
+    assert_eq!(problem.message().unwrap(), "The table USERS is missed");
+}
+
+#[test]
+fn test_warm_templates() {
+    assert!(custom::ColumnLimitMissed::warm_templates().is_ok());
+    assert!(custom::IndexMissed::warm_templates()
+        .unwrap_err()
+        .to_string()
+        .starts_with("Failed to render IndexMissed"));
+}
+
+#[test]
+fn test_problem_with_rollback_only() {
+    let problem = custom::TriggerLeft {
+        trigger_name: "audit".to_string(),
+        table_name: "users".to_string(),
+    };
+
+    assert!(problem.migration().is_none());
+    assert_eq!(
+        problem.rollback().unwrap().unwrap(),
+        "DROP TRIGGER audit ON users;"
+    );
+}
+
+#[test]
+fn test_custom_problem_severity() {
+    let problem = custom::TriggerLeft {
+        trigger_name: "audit".to_string(),
+        table_name: "users".to_string(),
+    };
+
+    assert_eq!(problem.severity(), Severity::Warning);
+}
+
+#[test]
+fn test_templates() {
+    let templates = custom::ColumnLimitMissedInspector::templates();
+
+    assert_eq!(templates.kind, "ColumnLimitMissed");
+    assert_eq!(
+        templates.message,
+        <custom::ColumnLimitMissed as CustomProblem>::message_()
+    );
+    assert!(templates.migration.unwrap().starts_with("ALTER TABLE"));
+    assert!(templates.rollback.unwrap().starts_with("ALTER TABLE"));
+    assert_eq!(
+        templates.query,
+        Some("SELECT * FROM table WHERE limit = {{ limit }};")
+    );
+    assert_eq!(custom::ColumnLimitMissed::templates().query, None);
+}
+
+#[test]
+fn test_render_all() {
+    let problem = custom::ColumnLimitMissed {
+        scope_name: "public".to_string(),
+        table_name: "users".to_string(),
+        column_name: "email".to_string(),
+        limit: 40,
+    };
+    let rendered = problem.render_all().unwrap();
+
+    assert_eq!(rendered.message, problem.message().unwrap());
+    assert_eq!(rendered.migration, problem.migration().transpose().unwrap());
+    assert_eq!(rendered.rollback, problem.rollback().transpose().unwrap());
+}
+
+#[test]
+fn test_problem_with_globals() {
+    let problem = custom::SchemaUndocumented {
+        schema_name: "audit".to_string(),
+    };
+
+    assert_eq!(
+        problem.message().unwrap(),
+        "The schema app.audit has no comment"
+    );
+}
+
+#[test]
+fn test_message_only_problem() {
+    let report = Report::from_iter(vec![
+        custom::SchemaUndocumented {
+            schema_name: "audit".to_string(),
+        },
+        custom::SchemaUndocumented {
+            schema_name: "billing".to_string(),
+        },
+    ]);
+
+    assert!(report.iter().all(|p| p.migration().is_none()));
+    assert!(report.iter().all(|p| p.rollback().is_none()));
+    assert_eq!(report.migration().unwrap(), "");
+    assert_eq!(report.rollback().unwrap(), "");
+    assert_eq!(
+        report.message().unwrap(),
+        "The schema app.audit has no comment\nThe schema app.billing has no comment"
+    );
+}
+
+#[test]
+fn test_fix() {
+    let problem = custom::ColumnLimitMissed {
+        scope_name: "public".to_string(),
+        table_name: "users".to_string(),
+        column_name: "email".to_string(),
+        limit: 40,
+    };
+    let (migration, rollback) = problem.fix().unwrap().unwrap();
+
+    assert_eq!(migration, problem.migration().unwrap().unwrap());
+    assert_eq!(rollback, problem.rollback().transpose().unwrap());
+}
+
+#[test]
+fn test_fix_missed() {
+    let problem = custom::SchemaUndocumented {
+        schema_name: "audit".to_string(),
+    };
+
+    assert!(problem.fix().is_none());
+}
+
+#[test]
+fn test_message_in_locale() {
+    let problem = custom::SchemaUndocumented {
+        schema_name: "audit".to_string(),
+    };
+
+    assert_eq!(
+        problem.message_in("de").unwrap(),
+        "Das Schema app.audit hat keinen Kommentar"
+    );
+    assert_eq!(
+        problem.message_in("fr").unwrap(),
+        "Le schéma app.audit n'a pas de commentaire"
+    );
+    assert_eq!(
+        problem.message_in("es").unwrap(),
+        "The schema app.audit has no comment"
+    );
+}
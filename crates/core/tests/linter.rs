@@ -0,0 +1,189 @@
+// Checks the code generated by the `#[linter]` macro
+// for the enum of hand-written inspectors,
+// and contexts derived by `#[derive(IntoContext)]`.
+use core::*;
+use macros::{linter, IntoContext};
+
+#[derive(Debug, Deserialize, FromRow, IntoContext)]
+pub struct TableMissed {
+    pub table_name: String,
+}
+
+impl CustomProblem for TableMissed {
+    type Client = PostgresClient;
+
+    fn kind_() -> &'static str {
+        "TableMissed"
+    }
+    fn message_() -> &'static str {
+        "The table {{ table_name }} is missed"
+    }
+    fn migration_() -> Option<&'static str> {
+        Some("CREATE TABLE {{ table_name }} ();")
+    }
+}
+
+#[derive(Debug, Deserialize, FromRow, IntoContext)]
+pub struct TableLeft {
+    pub table_name: String,
+}
+
+impl CustomProblem for TableLeft {
+    type Client = PostgresClient;
+
+    fn kind_() -> &'static str {
+        "TableLeft"
+    }
+    fn message_() -> &'static str {
+        "The table {{ table_name }} is left"
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, IntoContext)]
+pub struct TableMissedInspector {}
+
+impl CustomInspector for TableMissedInspector {
+    type Problem = TableMissed;
+
+    fn query_() -> &'static str {
+        "SELECT 'missed' AS table_name"
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, IntoContext)]
+pub struct TableLeftInspector {}
+
+impl CustomInspector for TableLeftInspector {
+    type Problem = TableLeft;
+
+    fn query_() -> &'static str {
+        "SELECT 'left' AS table_name"
+    }
+}
+
+// The hand-written problem identified by the name of the table only
+#[derive(Debug)]
+pub struct TableCopied {
+    pub table_name: String,
+}
+
+impl Problem for TableCopied {
+    type Client = PostgresClient;
+
+    fn kind(&self) -> &'static str {
+        "TableCopied"
+    }
+    fn message(&self) -> Result<String> {
+        Ok(format!("The table {} is copied", self.table_name))
+    }
+    fn migration(&self) -> Option<Result<String>> {
+        None
+    }
+    fn rollback(&self) -> Option<Result<String>> {
+        None
+    }
+    fn fingerprint(&self) -> Result<String> {
+        Ok(self.table_name.clone())
+    }
+}
+
+#[derive(Debug)]
+pub struct TableCopiedInspector;
+
+impl Inspector for TableCopiedInspector {
+    type Problem = TableCopied;
+
+    fn build(_key: &str, _value: &str) -> Result<Self> {
+        Ok(Self)
+    }
+    fn query(&self) -> Result<String> {
+        Ok("SELECT 'copied' AS table_name;".to_string())
+    }
+    fn parse(&self, row: <PostgresClient as Client>::Row) -> Result<TableCopied> {
+        Ok(TableCopied {
+            table_name: row.get("table_name"),
+        })
+    }
+}
+
+#[linter(problem = TestProblem)]
+#[derive(Debug)]
+pub enum TestInspector {
+    TableMissed(TableMissedInspector),
+    TableLeft(TableLeftInspector),
+    TableCopied(TableCopiedInspector),
+}
+
+#[test]
+fn build_inspectors() {
+    let inspector = TestInspector::build("TableLeft", "{}").unwrap();
+
+    assert!(matches!(inspector, TestInspector::TableLeft(_)));
+    assert_eq!(inspector.query().unwrap(), "SELECT 'left' AS table_name;");
+}
+
+// The same inspectors under variants named differently from the kinds
+#[linter(problem = RenamedProblem)]
+#[derive(Debug)]
+pub enum RenamedInspector {
+    Missed(TableMissedInspector),
+    Copied(TableCopiedInspector),
+}
+
+#[test]
+fn build_inspectors_by_kind() {
+    let inspector = RenamedInspector::build("TableMissed", "{}").unwrap();
+
+    assert!(matches!(inspector, RenamedInspector::Missed(_)));
+    assert!(RenamedInspector::build("Missed", "{}").is_err());
+}
+
+#[test]
+fn build_hand_written_inspectors_by_variant() {
+    let inspector = RenamedInspector::build("Copied", "{}").unwrap();
+
+    assert!(matches!(inspector, RenamedInspector::Copied(_)));
+}
+
+#[test]
+fn build_unknown_inspector() {
+    let error = TestInspector::build("TableRenamed", "{}").unwrap_err();
+
+    assert!(error.to_string().contains("TableRenamed"));
+}
+
+#[test]
+fn delegate_problems() {
+    let problem = TestProblem::TableMissed(TableMissed {
+        table_name: "users".to_string(),
+    });
+
+    assert_eq!(problem.kind(), "TableMissed");
+    assert_eq!(problem.message().unwrap(), "The table users is missed");
+    assert_eq!(
+        problem.migration().unwrap().unwrap(),
+        "CREATE TABLE users ();"
+    );
+
+    let problem = TestProblem::TableLeft(TableLeft {
+        table_name: "users".to_string(),
+    });
+
+    assert_eq!(problem.kind(), "TableLeft");
+    assert!(problem.migration().is_none());
+}
+
+#[test]
+fn delegate_fingerprints() {
+    let problem = TestProblem::TableCopied(TableCopied {
+        table_name: "users".to_string(),
+    });
+
+    assert_eq!(problem.fingerprint().unwrap(), "users");
+    assert_eq!(
+        problem.id().unwrap(),
+        "TableCopiedThe table users is copied"
+    );
+    assert!(problem.fix().is_none());
+    assert_eq!(problem.to_json_value().unwrap()["kind"], "TableCopied");
+}
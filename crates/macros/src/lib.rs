@@ -1,11 +1,11 @@
 /// This module only reexports macros:
 ///
-/// * `#[problem]` (with feature `problem`)
+/// * `#[problem]` and `#[derive(IntoContext)]` (with feature `problem`)
 /// * `#[linter]` (with default feature `linter`)
 ///
 /// Because linter uses problems defined by upstream crates,
 /// it is not possible to use both features at the same time.
-pub use macros_core::{inventory, Definition, Field, TaggedField};
+pub use macros_core::{inventory, problem_kind_enum, Definition, Field, TaggedField};
 /// Annotate problem definition with `#[problem(client="postgres", migration=false, rollback=false)]`.
 ///
 /// By default (when used as `#[problem]`), the client is set to "postgres",
@@ -13,12 +13,68 @@ pub use macros_core::{inventory, Definition, Field, TaggedField};
 /// If a migration is skipped (`#[problem(migration = false)]`), the rollback is also disabled,
 /// but a migration can be used without a rollback (`#[problem(rollback = false)]`).
 ///
+/// The severity of the problem is `"error"` by default,
+/// use `#[problem(severity = "warning")]` or `"info"` to lower it.
+///
+/// The kind of the problem is taken from the name of the structure,
+/// but can be set explicitly to rename the type without changing
+/// the public id of the rule: `#[problem(kind = "ColumnLimitMissed")]`.
+///
+/// The version the problem was introduced in can be recorded
+/// with `#[problem(since = "1.2.0")]` to be listed by tools iterating
+/// over the registered definitions.
+///
+/// The structure is kept with `Deserialize` and `FromRow` derived
+/// (unless derived explicitly) and `CustomProblem` implemented.
+/// The companion `<Name>Inspector` (with limits, and `only`/`except` lists
+/// of `<Name>Filter`) implements `CustomInspector` using the query template.
+/// Advisory problems which don't query the database (like the ones derived
+/// from other findings) are declared with `#[problem(query = false)]`:
+/// they have neither the query template nor the inspector, and cannot
+/// have filters or limits.
+/// The generated code refers to items of the `core` crate by their names,
+/// so they should be imported into the scope (`use core::*`)
+/// along with the `serde` and `postgres-from-row` dependencies.
+///
+/// Templates are read from the `templates/<snake_case_kind>/` directory
+/// next to the manifest of the crate. The directory can be relocated:
+/// `#[problem(templates = "sql/problems")]`. The names of template files
+/// can be changed as well: `#[problem(message_file = "msg.md", query_file = "q.sql")]`
+/// (along with `migration_file` and `rollback_file`).
+/// The compilation fails with the list of missing files if any template
+/// required by the problem (given the migration and rollback flags) is absent.
+///
+/// Placeholders of the message, migration and rollback templates are checked
+/// against the fields of the structure at compile time. Variables provided
+/// by other means (like globals) should be listed explicitly:
+/// `#[problem(context = ["schema"])]`.
+///
+/// The query must select every filter, and its placeholders
+/// can refer to filters and limits only.
+///
+/// With `#[problem(check_columns = true)]` the fields used by the message,
+/// migration and rollback are also checked to be selected by the query.
+/// Because SQL is not parsed for real, only the list between the leading
+/// `SELECT` and the first `FROM` is analyzed, taking either aliases (`AS name`)
+/// or the last identifiers (`t.name`) of columns. The check is skipped
+/// for `SELECT *` and the queries not starting with `SELECT` (like CTE),
+/// and subqueries in the list of columns can confuse it.
+///
 /// Fields of the structure can be optionally annotated with
 /// either `#[limit("description")]` or `#[filter("description")]` (but not both!)
 /// For annotated fields you should provide descriptions to be used
-/// in the generated config file like:
+/// in the generated config file (a bare `#[filter]` or `#[limit]`
+/// takes the description from the doc comment of the field).
+/// A limit can provide the default value to be shown in the config:
+/// `#[limit("description", default = 255)]`, and a filter can refer
+/// to a column whose name differs from the field in the WHERE clause:
+/// `#[filter("description", column = "nspname")]`. The operator
+/// of the filter is `=` unless set by `op` (one of `=`, `<>`, `!=`, `<`,
+/// `<=`, `>`, `>=`, `LIKE`, `ILIKE`, `NOT LIKE` and `NOT ILIKE`), so that
+/// `#[filter("description", op = ">=")]` emits `field >= value`. For example:
 ///
 /// ```rust
+/// # use core::*;
 /// # use macros::*;
 /// #[problem(migration = false)]
 /// pub struct TestColumnLimitMissed {
@@ -49,3 +105,59 @@ pub use macros_core::{inventory, Definition, Field, TaggedField};
 /// ```
 #[cfg(feature = "problem")]
 pub use macros_problem::problem;
+
+/// Derive `From<&T> for Context` inserting every named field
+/// of the structure under its name, as `CustomProblem`
+/// and `CustomInspector` expect:
+///
+/// ```ignore
+/// use core::*;
+///
+/// #[derive(Debug, Deserialize, FromRow, IntoContext)]
+/// pub struct TableMissed {
+///     pub table_name: String,
+/// }
+/// ```
+#[cfg(feature = "problem")]
+pub use macros_problem::IntoContext;
+
+/// Annotate the enum of inspectors with `#[linter(problem = TestProblem)]`
+/// to generate the enum of their problems along with
+/// implementations of `Inspector` and `Problem` for both enums.
+///
+/// Every variant should wrap exactly one inspector. The key of the rule
+/// in the config is the kind of the problem of the inspector
+/// (`Inspector::key`), or the name of the variant for inspectors
+/// which don't provide it (like hand-written ones):
+///
+/// ```ignore
+/// use core::*;
+///
+/// #[linter(problem = TestProblem)]
+/// #[derive(Debug)]
+/// pub enum TestInspector {
+///     ColumnLimitMissed(ColumnLimitMissedInspector),
+///     TableMissed(TableMissedInspector),
+/// }
+///
+/// pub struct TestLinter;
+///
+/// impl Linter for TestLinter {
+///     type Inspector = TestInspector;
+/// }
+/// ```
+///
+/// The generated code refers to items of the `core` crate by their names,
+/// so they must be imported into the scope of the enum.
+/// The client of the linter is taken from the problem of the first inspector,
+/// and the compilation fails (with `inspectors_share_the_client` in the message)
+/// when problems of other inspectors use another client.
+/// Every method of `Problem` (including the `fingerprint` used to remove
+/// duplicates from reports) is delegated to the wrapped problem.
+///
+/// The variants are listed explicitly rather than collected from the registered
+/// definitions, because the `inventory` of definitions is only available at runtime,
+/// while the macro is expanded at compile time (and cannot see problems defined
+/// by other crates). Use `problem_kind_enum` to check the list against the registry.
+#[cfg(feature = "linter")]
+pub use macros_linter::linter;
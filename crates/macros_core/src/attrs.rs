@@ -1,19 +1,55 @@
 use crate::client::Client;
 use crate::flag::Flag;
+use crate::severity::Severity;
 use quote::ToTokens;
 use syn::{
     parse::{Parse, ParseStream},
     parse2,
     punctuated::Punctuated,
-    Error, ExprAssign, Token,
+    Error, Expr, ExprAssign, ExprLit, Lit, LitStr, Token,
 };
 
-/// Parse the `#[problem(client="postgres", migration=false, rollback=false)]` attributes.
+/// Parse the `#[problem(client="postgres", migration=false, rollback=false, severity="error")]` attributes.
+///
+/// The `message` and `query` keys take inline templates,
+/// which are used instead of the files from the templates directory.
+/// The `query = false` declares a message-only problem without a query.
+///
+/// The `check_columns = true` enables the check that the fields used
+/// by templates are selected by the query (see `macros::problem` for limitations).
+///
+/// The `kind = "Name"` sets the public name of the problem
+/// used in configs instead of the name of the structure.
+///
+/// The `templates = "path"` sets the directory with templates
+/// relative to the manifest of the crate (`templates` by default).
+///
+/// The `message_file`, `query_file`, `migration_file` and `rollback_file` keys
+/// override the default names of template files (like `message.txt`).
+///
+/// The `context = ["key", ...]` lists additional variables the templates
+/// are allowed to refer to besides the fields of the problem
+/// (like globals or variables computed by the problem itself).
+///
+/// The `since = "1.2.0"` records the version the problem was introduced in.
 #[derive(Default)]
 pub struct Attrs {
+    check_columns: bool,
     client: Client,
+    context: Vec<String>,
+    kind: Option<String>,
+    message: Option<String>,
+    message_file: Option<String>,
     migration: Flag,
+    migration_file: Option<String>,
+    query: Option<String>,
+    queried: Flag,
+    query_file: Option<String>,
     rollback: Flag,
+    rollback_file: Option<String>,
+    severity: Severity,
+    since: Option<String>,
+    templates: Option<String>,
 }
 
 impl Parse for Attrs {
@@ -22,9 +58,29 @@ impl Parse for Attrs {
         for item in Punctuated::<ExprAssign, Token![,]>::parse_terminated(input)? {
             let key: String = item.left.to_token_stream().to_string();
             match key.as_str() {
+                "check_columns" => {
+                    output.check_columns = parse2::<Flag>(item.right.to_token_stream())?.into()
+                }
                 "client" => output.client = parse2(item.right.to_token_stream())?,
+                "context" => output.context = context(&item.right)?,
+                "kind" => output.kind = Some(string(&item.right)?),
+                "message_file" => output.message_file = Some(string(&item.right)?),
+                "migration_file" => output.migration_file = Some(string(&item.right)?),
+                "query_file" => output.query_file = Some(string(&item.right)?),
+                "rollback_file" => output.rollback_file = Some(string(&item.right)?),
+                "message" => output.message = Some(string(&item.right)?),
                 "migration" => output.migration = parse2(item.right.to_token_stream())?,
+                "query" => match &*item.right {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Bool(flag),
+                        ..
+                    }) => output.queried = flag.value.into(),
+                    expr => output.query = Some(string(expr)?),
+                },
                 "rollback" => output.rollback = parse2(item.right.to_token_stream())?,
+                "severity" => output.severity = parse2(item.right.to_token_stream())?,
+                "since" => output.since = Some(string(&item.right)?),
+                "templates" => output.templates = Some(string(&item.right)?),
                 _ => return Err(Error::new_spanned(&item, "Unknown attribute")),
             }
         }
@@ -34,10 +90,50 @@ impl Parse for Attrs {
 }
 
 impl Attrs {
+    pub fn check_columns(&self) -> bool {
+        self.check_columns
+    }
+
     pub fn client(&self) -> &'static str {
         self.client.into()
     }
 
+    pub fn context(&self) -> &[String] {
+        &self.context
+    }
+
+    pub fn kind(&self) -> Option<&str> {
+        self.kind.as_deref()
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    pub fn queried(&self) -> bool {
+        self.queried.into()
+    }
+
+    pub fn message_file(&self) -> &str {
+        self.message_file.as_deref().unwrap_or("message.txt")
+    }
+
+    pub fn query_file(&self) -> &str {
+        self.query_file.as_deref().unwrap_or("query.sql")
+    }
+
+    pub fn migration_file(&self) -> &str {
+        self.migration_file.as_deref().unwrap_or("migration.sql")
+    }
+
+    pub fn rollback_file(&self) -> &str {
+        self.rollback_file.as_deref().unwrap_or("rollback.sql")
+    }
+
     pub fn migration(&self) -> bool {
         self.migration.into()
     }
@@ -45,6 +141,35 @@ impl Attrs {
     pub fn rollback(&self) -> bool {
         self.rollback.into()
     }
+
+    pub fn severity(&self) -> &'static str {
+        self.severity.into()
+    }
+
+    pub fn since(&self) -> Option<&str> {
+        self.since.as_deref()
+    }
+
+    pub fn templates(&self) -> Option<&str> {
+        self.templates.as_deref()
+    }
+}
+
+// Parse the string literal
+fn string(expr: &Expr) -> syn::Result<String> {
+    parse2::<LitStr>(expr.to_token_stream()).map(|s| s.value())
+}
+
+// Parse the list of additional context keys `["key", ...]`
+fn context(expr: &Expr) -> syn::Result<Vec<String>> {
+    match expr {
+        Expr::Array(array) => array
+            .elems
+            .iter()
+            .map(|e| parse2::<LitStr>(e.to_token_stream()).map(|s| s.value()))
+            .collect(),
+        _ => Err(Error::new_spanned(expr, "A list of strings expected")),
+    }
 }
 
 #[cfg(test)]
@@ -58,8 +183,135 @@ mod test {
         let attrs = parse2::<Attrs>(input).unwrap();
 
         assert_eq!(attrs.client(), "PostgresClient");
-        assert_eq!(attrs.migration(), true);
-        assert_eq!(attrs.rollback(), true);
+        assert!(attrs.migration());
+        assert!(attrs.rollback());
+        assert_eq!(attrs.message(), None);
+        assert_eq!(attrs.query(), None);
+        assert!(attrs.queried());
+        assert_eq!(attrs.severity(), "error");
+        assert_eq!(attrs.since(), None);
+        assert_eq!(attrs.templates(), None);
+        assert_eq!(attrs.kind(), None);
+        assert!(!attrs.check_columns());
+        assert_eq!(attrs.message_file(), "message.txt");
+        assert_eq!(attrs.query_file(), "query.sql");
+        assert_eq!(attrs.migration_file(), "migration.sql");
+        assert_eq!(attrs.rollback_file(), "rollback.sql");
+    }
+
+    #[test]
+    fn template_files() {
+        let input = parse_quote! {
+            message_file = "msg.md",
+            query_file = "q.sql",
+            migration_file = "up.sql.tera",
+            rollback_file = "down.sql.tera",
+        };
+        let attrs = parse2::<Attrs>(input).unwrap();
+
+        assert_eq!(attrs.message_file(), "msg.md");
+        assert_eq!(attrs.query_file(), "q.sql");
+        assert_eq!(attrs.migration_file(), "up.sql.tera");
+        assert_eq!(attrs.rollback_file(), "down.sql.tera");
+    }
+
+    #[test]
+    fn check_columns() {
+        let input = parse_quote! { check_columns = true };
+        let attrs = parse2::<Attrs>(input).unwrap();
+
+        assert!(attrs.check_columns());
+    }
+
+    #[test]
+    fn kind() {
+        let input = parse_quote! { kind = "ColumnLimitMissed" };
+        let attrs = parse2::<Attrs>(input).unwrap();
+
+        assert_eq!(attrs.kind(), Some("ColumnLimitMissed"));
+    }
+
+    #[test]
+    fn templates() {
+        let input = parse_quote! { templates = "sql/problems" };
+        let attrs = parse2::<Attrs>(input).unwrap();
+
+        assert_eq!(attrs.templates(), Some("sql/problems"));
+    }
+
+    #[test]
+    fn since() {
+        let input = parse_quote! { since = "1.2.0" };
+        let attrs = parse2::<Attrs>(input).unwrap();
+
+        assert_eq!(attrs.since(), Some("1.2.0"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_string_since() {
+        let input = parse_quote! { since = 1.2 };
+        parse2::<Attrs>(input).unwrap();
+    }
+
+    #[test]
+    fn severity() {
+        for (input, output) in [
+            (parse_quote! { severity = "info" }, "info"),
+            (parse_quote! { severity = "warning" }, "warning"),
+            (parse_quote! { severity = "error" }, "error"),
+        ] {
+            let attrs = parse2::<Attrs>(input).unwrap();
+
+            assert_eq!(attrs.severity(), output);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn unknown_severity() {
+        let input = parse_quote! { severity = "fatal" };
+        parse2::<Attrs>(input).unwrap();
+    }
+
+    #[test]
+    fn inline_templates() {
+        let input = parse_quote! { message = "Table {{ name }}", query = "SELECT 1" };
+        let attrs = parse2::<Attrs>(input).unwrap();
+
+        assert_eq!(attrs.message(), Some("Table {{ name }}"));
+        assert_eq!(attrs.query(), Some("SELECT 1"));
+    }
+
+    #[test]
+    fn without_query() {
+        let input = parse_quote! { query = false };
+        let attrs = parse2::<Attrs>(input).unwrap();
+
+        assert!(!attrs.queried());
+        assert_eq!(attrs.query(), None);
+    }
+
+    #[test]
+    fn context() {
+        let input = parse_quote! { context = ["now", "schema"] };
+        let attrs = parse2::<Attrs>(input).unwrap();
+
+        assert_eq!(attrs.context(), ["now", "schema"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_list_context() {
+        let input = parse_quote! { context = "now" };
+        parse2::<Attrs>(input).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_string_message() {
+        let input = parse_quote! { message = 42 };
+        parse2::<Attrs>(input).unwrap();
     }
 
     #[test]
@@ -68,8 +320,8 @@ mod test {
         let attrs = parse2::<Attrs>(input).unwrap();
 
         assert_eq!(attrs.client(), "PostgresClient");
-        assert_eq!(attrs.migration(), true);
-        assert_eq!(attrs.rollback(), false);
+        assert!(attrs.migration());
+        assert!(!attrs.rollback());
     }
 
     #[test]
@@ -78,8 +330,8 @@ mod test {
         let attrs = parse2::<Attrs>(input).unwrap();
 
         assert_eq!(attrs.client(), "PostgresClient");
-        assert_eq!(attrs.migration(), false);
-        assert_eq!(attrs.rollback(), false);
+        assert!(!attrs.migration());
+        assert!(!attrs.rollback());
     }
 
     #[test]
@@ -98,8 +350,15 @@ mod test {
 
     #[test]
     #[should_panic]
-    fn non_postgres_client() {
+    fn mysql() {
         let input = parse_quote! { client = "mysql" };
         parse2::<Attrs>(input).unwrap();
     }
+
+    #[test]
+    #[should_panic]
+    fn unknown_client() {
+        let input = parse_quote! { client = "oracle" };
+        parse2::<Attrs>(input).unwrap();
+    }
 }
@@ -6,6 +6,7 @@ use syn::{
 
 /// Parses and validates a client attribute
 /// `"postgres"` -> `Client(PostgresClient)`
+/// (`"mysql"` is reserved, but rejected until the core crate defines the client)
 #[derive(Copy, Clone)]
 pub(crate) struct Client(&'static str);
 
@@ -17,14 +18,18 @@ impl Default for Client {
 
 impl Parse for Client {
     fn parse(input: ParseStream) -> Result<Self> {
-        let key = input
+        let (key, span) = input
             .parse::<Literal>()
-            .map(|x| x.to_string().replace("\"", ""))
-            .or_else(|_| input.parse::<Ident>().map(|x| x.to_string()))?;
+            .map(|x| (x.to_string().replace("\"", ""), x.span()))
+            .or_else(|_| input.parse::<Ident>().map(|x| (x.to_string(), x.span())))?;
 
-        match key {
-            key if key.as_str() == "postgres" => Ok(Self::default()),
-            _ => Err(syn::Error::new_spanned(key, "Unknown client")),
+        match key.as_str() {
+            "postgres" => Ok(Self::default()),
+            "mysql" => Err(syn::Error::new(
+                span,
+                "The mysql client is not supported yet",
+            )),
+            _ => Err(syn::Error::new(span, "Unknown client")),
         }
     }
 }
@@ -64,6 +69,21 @@ mod test_client {
         assert_eq!(client, "PostgresClient");
     }
 
+    #[test]
+    fn mysql() {
+        let input = quote! { "mysql" };
+        let error = parse2::<Client>(input).err().unwrap();
+
+        assert_eq!(error.to_string(), "The mysql client is not supported yet");
+    }
+
+    #[test]
+    #[should_panic]
+    fn unquoted_mysql() {
+        let input = quote! { mysql };
+        parse2::<Client>(input).unwrap();
+    }
+
     #[test]
     #[should_panic]
     fn unknown() {
@@ -52,7 +52,7 @@ mod test_client {
     fn default() {
         let client: bool = Flag::default().into();
 
-        assert_eq!(client, true);
+        assert!(client);
     }
 
     #[test]
@@ -60,7 +60,7 @@ mod test_client {
         let input = quote! { "true" };
         let flag: bool = parse2::<Flag>(input).unwrap().into();
 
-        assert_eq!(flag, true);
+        assert!(flag);
     }
 
     #[test]
@@ -68,7 +68,7 @@ mod test_client {
         let input = quote! { true };
         let flag: bool = parse2::<Flag>(input).unwrap().into();
 
-        assert_eq!(flag, true);
+        assert!(flag);
     }
 
     #[test]
@@ -76,7 +76,7 @@ mod test_client {
         let input = quote! { "false" };
         let flag: bool = parse2::<Flag>(input).unwrap().into();
 
-        assert_eq!(flag, false);
+        assert!(!flag);
     }
 
     #[test]
@@ -84,7 +84,7 @@ mod test_client {
         let input = quote! { false };
         let flag: bool = parse2::<Flag>(input).unwrap().into();
 
-        assert_eq!(flag, false);
+        assert!(!flag);
     }
 
     #[test]
@@ -0,0 +1,91 @@
+use crate::Definition;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::{parse_str, Error, Result};
+
+/// Generate the source of the `ProblemKind` enum of all problems registered in the inventory
+/// with `as_str` and `FromStr` for exhaustive matching on kinds.
+///
+/// Because the inventory is collected at link time, the enum cannot be produced by a macro.
+/// Instead, call the function from a build script that depends on crates with problems,
+/// write the source to the `OUT_DIR` and `include!` it:
+///
+/// ```ignore
+/// // build.rs
+/// let path = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("problem_kind.rs");
+/// std::fs::write(path, macros::problem_kind_enum().unwrap()).unwrap();
+///
+/// // lib.rs
+/// include!(concat!(env!("OUT_DIR"), "/problem_kind.rs"));
+/// ```
+///
+/// Variants are sorted by name. Every kind must be a valid identifier,
+/// otherwise the error names the first kind that is not.
+pub fn problem_kind_enum() -> Result<String> {
+    let mut kinds: Vec<_> = inventory::iter::<Definition>().map(|d| d.name).collect();
+    kinds.sort_unstable();
+    kinds.dedup();
+    expand(&kinds).map(|tokens| tokens.to_string())
+}
+
+fn expand(kinds: &[&str]) -> Result<TokenStream> {
+    let variants = kinds
+        .iter()
+        .map(|kind| {
+            parse_str::<Ident>(kind).map_err(|_| {
+                let msg = format!("The kind {:?} is not a valid Rust identifier", kind);
+                Error::new(Span::call_site(), msg)
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        pub enum ProblemKind {
+            #(#variants,)*
+        }
+
+        impl ProblemKind {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    #(Self::#variants => #kinds,)*
+                }
+            }
+        }
+
+        impl std::str::FromStr for ProblemKind {
+            type Err = String;
+
+            fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+                match s {
+                    #(#kinds => Ok(Self::#variants),)*
+                    _ => Err(s.to_string()),
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn valid_kinds() {
+        let output = expand(&["ColumnLimitMissed", "TableMissed"])
+            .unwrap()
+            .to_string();
+
+        assert!(output.contains("ColumnLimitMissed , TableMissed ,"));
+    }
+
+    #[test]
+    fn invalid_kind() {
+        let error = expand(&["TableMissed", "table-missed"]).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "The kind \"table-missed\" is not a valid Rust identifier"
+        );
+    }
+}
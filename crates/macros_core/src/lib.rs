@@ -1,13 +1,34 @@
 mod attrs;
 mod client;
 mod flag;
+mod kinds;
+mod severity;
 
 /// Provide structure for parsing problem definitions
 pub use attrs::Attrs;
 
+/// Generate the enum of all registered problem kinds
+pub use kinds::problem_kind_enum;
+
 /// Reexport inventory crate that collects problem definitions.
 pub use inventory;
 
+/// SQL operators allowed in filters (`#[filter("description", op = ">=")]`),
+/// shared by the macro validating them and the inspector rendering WHERE clauses.
+pub const OPERATORS: &[&str] = &[
+    "=",
+    "<>",
+    "!=",
+    "<",
+    "<=",
+    ">",
+    ">=",
+    "LIKE",
+    "ILIKE",
+    "NOT LIKE",
+    "NOT ILIKE",
+];
+
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq)]
 pub struct Definition {
@@ -18,8 +39,11 @@ pub struct Definition {
     pub message: &'static str,
     pub migration: Option<&'static str>,
     pub name: &'static str,
-    pub query: &'static str,
+    pub query: Option<&'static str>,
     pub rollback: Option<&'static str>,
+    pub severity: &'static str,
+    /// The version the problem was introduced in
+    pub since: Option<&'static str>,
 }
 
 #[repr(C)]
@@ -35,6 +59,12 @@ pub struct TaggedField {
     pub name: &'static str,
     pub ty: &'static str,
     pub desc: &'static str,
+    /// The default value of the limit to be shown in the config
+    pub default: Option<&'static str>,
+    /// The column of the filter used in the WHERE clause (the name by default)
+    pub column: Option<&'static str>,
+    /// The operator of the filter used in the WHERE clause (`=` by default)
+    pub op: Option<&'static str>,
 }
 
 inventory::collect!(Definition);
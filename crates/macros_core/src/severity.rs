@@ -0,0 +1,72 @@
+use proc_macro2::{Ident, Literal};
+use syn::{
+    parse::{Parse, ParseStream},
+    Result,
+};
+
+/// Parses and validates a severity attribute
+/// `"warning"` -> `Severity("warning")`
+#[derive(Copy, Clone)]
+pub(crate) struct Severity(&'static str);
+
+impl Default for Severity {
+    fn default() -> Self {
+        Self("error")
+    }
+}
+
+impl Parse for Severity {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let key = input
+            .parse::<Literal>()
+            .map(|x| x.to_string().replace("\"", ""))
+            .or_else(|_| input.parse::<Ident>().map(|x| x.to_string()))?;
+
+        match key.as_str() {
+            "info" => Ok(Self("info")),
+            "warning" => Ok(Self("warning")),
+            "error" => Ok(Self("error")),
+            _ => Err(syn::Error::new_spanned(key, "Unknown severity")),
+        }
+    }
+}
+
+impl From<Severity> for &'static str {
+    fn from(severity: Severity) -> Self {
+        severity.0
+    }
+}
+
+#[cfg(test)]
+mod test_severity {
+    use super::*;
+    use quote::quote;
+    use syn::parse2;
+
+    #[test]
+    fn default() {
+        let severity: &str = Severity::default().into();
+
+        assert_eq!(severity, "error");
+    }
+
+    #[test]
+    fn known() {
+        for (input, output) in [
+            (quote! { "info" }, "info"),
+            (quote! { "warning" }, "warning"),
+            (quote! { error }, "error"),
+        ] {
+            let severity: &str = parse2::<Severity>(input).unwrap().into();
+
+            assert_eq!(severity, output);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn unknown() {
+        let input = quote! { "fatal" };
+        parse2::<Severity>(input).unwrap();
+    }
+}
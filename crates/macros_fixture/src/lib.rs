@@ -2,22 +2,25 @@
 /// to check results of macro expansion in a downstream crate.
 pub use macros::*;
 
-/// See the templates at the root `./templates` folder.
+#[cfg(feature = "problem")]
+use core::*;
+
+/// See the templates at the `./templates` folder of the crate.
 
 #[cfg(feature = "problem")]
-#[problem]
+#[problem(check_columns = true, since = "0.1.0")]
 pub struct TestColumnLimitMissed {
-    #[filter("The scope of the database table")]
+    #[filter("The scope of the database table", column = "nspname")]
     pub scope_name: String,
     #[filter("The name of the table")]
     pub table_name: String,
     pub column_name: String,
-    #[limit("The max number of chars allowed in the column")]
+    #[limit("The max number of chars allowed in the column", default = 255)]
     pub limit: u32,
 }
 
 #[cfg(feature = "problem")]
-#[problem(migration = false)]
+#[problem(migration = false, severity = "warning")]
 pub struct TestPrimaryKeyMissed {
     #[filter("The scope of the database table")]
     pub scope_name: String,
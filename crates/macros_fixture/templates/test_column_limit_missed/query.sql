@@ -1,10 +1,12 @@
-SELECT t.relnamespace::regnamespace AS scope_name,
+SELECT n.nspname AS scope_name,
        a.attrelid::regclass AS table_name,
        a.attname AS column_name,
        {{ limit }} AS limit
 FROM pg_attribute a
     INNER JOIN pg_class t
         ON a.attrelid = t.oid
+    INNER JOIN pg_namespace n
+        ON n.oid = t.relnamespace
     LEFT OUTER JOIN pg_constraint c
         ON c.conrelid = a.attrelid
         AND c.conkey = a.attnum
@@ -0,0 +1,51 @@
+use proc_macro2::Ident;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Token,
+};
+
+/// Parse the `#[linter(problem = TestProblem)]` attributes
+/// with the name of the problem enum to be generated.
+pub struct Attrs {
+    pub problem: Ident,
+}
+
+impl Parse for Attrs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse::<Ident>()?;
+        if key != "problem" {
+            return Err(Error::new_spanned(key, "Unknown attribute"));
+        }
+        input.parse::<Token![=]>()?;
+        let problem = input.parse::<Ident>()?;
+        Ok(Self { problem })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use syn::{parse2, parse_quote};
+
+    #[test]
+    fn problem() {
+        let input = parse_quote! { problem = TestProblem };
+        let attrs = parse2::<Attrs>(input).unwrap();
+
+        assert_eq!(attrs.problem, "TestProblem");
+    }
+
+    #[test]
+    #[should_panic]
+    fn missed_problem() {
+        let input = parse_quote! {};
+        parse2::<Attrs>(input).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn unknown_attribute() {
+        let input = parse_quote! { foo = TestProblem };
+        parse2::<Attrs>(input).unwrap();
+    }
+}
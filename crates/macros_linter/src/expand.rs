@@ -0,0 +1,269 @@
+use crate::attrs::Attrs;
+use crate::item::Item;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::parse2;
+use syn::spanned::Spanned;
+
+/// Expand the enums, or report the error via `compile_error!`.
+pub fn expand(attrs: TokenStream, item: TokenStream) -> TokenStream {
+    try_expand(attrs, item).unwrap_or_else(|err| err.to_compile_error())
+}
+
+fn try_expand(attrs: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let attrs: Attrs = parse2(attrs)?;
+    let problem = &attrs.problem;
+
+    let Item { item, variants } = parse2(item)?;
+    let vis = &item.vis;
+    let inspector = &item.ident;
+    let names: Vec<_> = variants.iter().map(|(name, _)| name).collect();
+    let keys: Vec<_> = names.iter().map(|name| name.to_string()).collect();
+    let types: Vec<_> = variants.iter().map(|(_, ty)| ty).collect();
+    let first = types[0];
+    let checks = types.iter().map(|ty| {
+        quote_spanned! { ty.span()=> inspectors_share_the_client::<#ty>(); }
+    });
+
+    Ok(quote! {
+        #item
+
+        #[derive(Debug)]
+        #vis enum #problem {
+            #(#names(<#types as Inspector>::Problem),)*
+        }
+
+        impl Problem for #problem {
+            type Client = <<#first as Inspector>::Problem as Problem>::Client;
+
+            fn kind(&self) -> &'static str {
+                match self { #(Self::#names(p) => p.kind(),)* }
+            }
+            fn message(&self) -> Result<String> {
+                match self { #(Self::#names(p) => p.message(),)* }
+            }
+            fn message_in(&self, locale: &str) -> Result<String> {
+                match self { #(Self::#names(p) => p.message_in(locale),)* }
+            }
+            fn migration(&self) -> Option<Result<String>> {
+                match self { #(Self::#names(p) => p.migration(),)* }
+            }
+            fn rollback(&self) -> Option<Result<String>> {
+                match self { #(Self::#names(p) => p.rollback(),)* }
+            }
+            fn severity(&self) -> Severity {
+                match self { #(Self::#names(p) => p.severity(),)* }
+            }
+            fn category(&self) -> &'static str {
+                match self { #(Self::#names(p) => p.category(),)* }
+            }
+            fn tags(&self) -> &'static [&'static str] {
+                match self { #(Self::#names(p) => p.tags(),)* }
+            }
+            fn docs_url(&self) -> Option<&'static str> {
+                match self { #(Self::#names(p) => p.docs_url(),)* }
+            }
+            fn target(&self) -> Option<(String, String)> {
+                match self { #(Self::#names(p) => p.target(),)* }
+            }
+            fn depends_on(&self) -> Vec<String> {
+                match self { #(Self::#names(p) => p.depends_on(),)* }
+            }
+            fn id(&self) -> Result<String> {
+                match self { #(Self::#names(p) => p.id(),)* }
+            }
+            fn render_all(&self) -> Result<Rendered> {
+                match self { #(Self::#names(p) => p.render_all(),)* }
+            }
+            fn fix(&self) -> Option<Result<(String, Option<String>)>> {
+                match self { #(Self::#names(p) => p.fix(),)* }
+            }
+            fn to_json_value(&self) -> Result<Value> {
+                match self { #(Self::#names(p) => p.to_json_value(),)* }
+            }
+            fn fingerprint(&self) -> Result<String> {
+                match self { #(Self::#names(p) => p.fingerprint(),)* }
+            }
+        }
+
+        impl Inspector for #inspector {
+            type Problem = #problem;
+
+            fn build(key: &str, value: &str) -> Result<Self> {
+                #(
+                    if key == <#types as Inspector>::key().unwrap_or(#keys) {
+                        return Ok(Self::#names(<#types as Inspector>::build(key, value)?));
+                    }
+                )*
+                Err(key.to_string().into())
+            }
+            fn query(&self) -> Result<String> {
+                match self { #(Self::#names(i) => i.query(),)* }
+            }
+            fn parse(
+                &self,
+                row: <<Self::Problem as Problem>::Client as Client>::Row,
+            ) -> Result<Self::Problem> {
+                match self { #(Self::#names(i) => i.parse(row).map(#problem::#names),)* }
+            }
+            fn applies_to_client(&self, client: &'static str) -> bool {
+                match self { #(Self::#names(i) => i.applies_to_client(client),)* }
+            }
+        }
+
+        const _: () = {
+            fn inspectors_share_the_client<I>()
+            where
+                I: Inspector,
+                I::Problem: Problem<Client = <#problem as Problem>::Client>,
+            {
+            }
+            fn check() {
+                #(#checks)*
+            }
+        };
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn expand_linter() {
+        let attrs = quote! { problem = TestProblem };
+        let item = quote! {
+            pub enum TestInspector {
+                Foo(FooInspector),
+                Bar(BarInspector),
+            }
+        };
+        let output = expand(attrs, item);
+        let target = quote! {
+            pub enum TestInspector {
+                Foo(FooInspector),
+                Bar(BarInspector),
+            }
+
+            #[derive(Debug)]
+            pub enum TestProblem {
+                Foo(<FooInspector as Inspector>::Problem),
+                Bar(<BarInspector as Inspector>::Problem),
+            }
+
+            impl Problem for TestProblem {
+                type Client = <<FooInspector as Inspector>::Problem as Problem>::Client;
+
+                fn kind(&self) -> &'static str {
+                    match self { Self::Foo(p) => p.kind(), Self::Bar(p) => p.kind(), }
+                }
+                fn message(&self) -> Result<String> {
+                    match self { Self::Foo(p) => p.message(), Self::Bar(p) => p.message(), }
+                }
+                fn message_in(&self, locale: &str) -> Result<String> {
+                    match self {
+                        Self::Foo(p) => p.message_in(locale),
+                        Self::Bar(p) => p.message_in(locale),
+                    }
+                }
+                fn migration(&self) -> Option<Result<String>> {
+                    match self { Self::Foo(p) => p.migration(), Self::Bar(p) => p.migration(), }
+                }
+                fn rollback(&self) -> Option<Result<String>> {
+                    match self { Self::Foo(p) => p.rollback(), Self::Bar(p) => p.rollback(), }
+                }
+                fn severity(&self) -> Severity {
+                    match self { Self::Foo(p) => p.severity(), Self::Bar(p) => p.severity(), }
+                }
+                fn category(&self) -> &'static str {
+                    match self { Self::Foo(p) => p.category(), Self::Bar(p) => p.category(), }
+                }
+                fn tags(&self) -> &'static [&'static str] {
+                    match self { Self::Foo(p) => p.tags(), Self::Bar(p) => p.tags(), }
+                }
+                fn docs_url(&self) -> Option<&'static str> {
+                    match self { Self::Foo(p) => p.docs_url(), Self::Bar(p) => p.docs_url(), }
+                }
+                fn target(&self) -> Option<(String, String)> {
+                    match self { Self::Foo(p) => p.target(), Self::Bar(p) => p.target(), }
+                }
+                fn depends_on(&self) -> Vec<String> {
+                    match self { Self::Foo(p) => p.depends_on(), Self::Bar(p) => p.depends_on(), }
+                }
+                fn id(&self) -> Result<String> {
+                    match self { Self::Foo(p) => p.id(), Self::Bar(p) => p.id(), }
+                }
+                fn render_all(&self) -> Result<Rendered> {
+                    match self { Self::Foo(p) => p.render_all(), Self::Bar(p) => p.render_all(), }
+                }
+                fn fix(&self) -> Option<Result<(String, Option<String>)>> {
+                    match self { Self::Foo(p) => p.fix(), Self::Bar(p) => p.fix(), }
+                }
+                fn to_json_value(&self) -> Result<Value> {
+                    match self {
+                        Self::Foo(p) => p.to_json_value(),
+                        Self::Bar(p) => p.to_json_value(),
+                    }
+                }
+                fn fingerprint(&self) -> Result<String> {
+                    match self { Self::Foo(p) => p.fingerprint(), Self::Bar(p) => p.fingerprint(), }
+                }
+            }
+
+            impl Inspector for TestInspector {
+                type Problem = TestProblem;
+
+                fn build(key: &str, value: &str) -> Result<Self> {
+                    if key == <FooInspector as Inspector>::key().unwrap_or("Foo") {
+                        return Ok(Self::Foo(<FooInspector as Inspector>::build(key, value)?));
+                    }
+                    if key == <BarInspector as Inspector>::key().unwrap_or("Bar") {
+                        return Ok(Self::Bar(<BarInspector as Inspector>::build(key, value)?));
+                    }
+                    Err(key.to_string().into())
+                }
+                fn query(&self) -> Result<String> {
+                    match self { Self::Foo(i) => i.query(), Self::Bar(i) => i.query(), }
+                }
+                fn parse(
+                    &self,
+                    row: <<Self::Problem as Problem>::Client as Client>::Row,
+                ) -> Result<Self::Problem> {
+                    match self {
+                        Self::Foo(i) => i.parse(row).map(TestProblem::Foo),
+                        Self::Bar(i) => i.parse(row).map(TestProblem::Bar),
+                    }
+                }
+                fn applies_to_client(&self, client: &'static str) -> bool {
+                    match self {
+                        Self::Foo(i) => i.applies_to_client(client),
+                        Self::Bar(i) => i.applies_to_client(client),
+                    }
+                }
+            }
+
+            const _: () = {
+                fn inspectors_share_the_client<I>()
+                where
+                    I: Inspector,
+                    I::Problem: Problem<Client = <TestProblem as Problem>::Client>,
+                {
+                }
+                fn check() {
+                    inspectors_share_the_client::<FooInspector>();
+                    inspectors_share_the_client::<BarInspector>();
+                }
+            };
+        };
+        assert_eq!(output.to_string(), target.to_string());
+    }
+
+    #[test]
+    fn empty_enum() {
+        let attrs = quote! { problem = TestProblem };
+        let output = expand(attrs, quote! { pub enum TestInspector {} }).to_string();
+
+        assert!(output.contains("compile_error !"));
+        assert!(output.contains("at least one inspector expected"));
+    }
+}
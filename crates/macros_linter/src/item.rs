@@ -0,0 +1,81 @@
+use proc_macro2::Ident;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Fields, ItemEnum, Type,
+};
+
+/// Parse the enum of inspectors where every variant wraps exactly one inspector:
+/// `ColumnLimitMissed(ColumnLimitMissedInspector)`.
+/// The name of the variant is used as a key of the rule in the config.
+pub struct Item {
+    pub item: ItemEnum,
+    pub variants: Vec<(Ident, Type)>,
+}
+
+impl Parse for Item {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let item = input.parse::<ItemEnum>()?;
+        let mut variants = Vec::with_capacity(item.variants.len());
+        for v in item.variants.iter() {
+            match &v.fields {
+                Fields::Unnamed(f) if f.unnamed.len() == 1 => {
+                    variants.push((v.ident.clone(), f.unnamed[0].ty.clone()))
+                }
+                _ => return Err(Error::new_spanned(v, "a single inspector expected")),
+            }
+        }
+        if variants.is_empty() {
+            return Err(Error::new_spanned(&item, "at least one inspector expected"));
+        }
+        Ok(Self { item, variants })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use syn::{parse2, parse_quote};
+
+    #[test]
+    fn valid_item() {
+        let input = parse_quote! {
+            pub enum TestInspector {
+                Foo(FooInspector),
+                Bar(BarInspector),
+            }
+        };
+        let output = parse2::<Item>(input).unwrap();
+
+        let names: Vec<_> = output.variants.iter().map(|(n, _)| n.to_string()).collect();
+        assert_eq!(names, ["Foo", "Bar"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_enum() {
+        let input = parse_quote! {
+            pub enum TestInspector {}
+        };
+        parse2::<Item>(input).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn unit_variant() {
+        let input = parse_quote! {
+            pub enum TestInspector {
+                Foo,
+            }
+        };
+        parse2::<Item>(input).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_an_enum() {
+        let input = parse_quote! {
+            pub struct TestInspector {}
+        };
+        parse2::<Item>(input).unwrap();
+    }
+}
@@ -0,0 +1,11 @@
+mod attrs;
+mod expand;
+mod item;
+
+use expand::expand;
+use proc_macro::TokenStream;
+
+#[proc_macro_attribute]
+pub fn linter(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand(attr.into(), item.into()).into()
+}
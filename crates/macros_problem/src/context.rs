@@ -0,0 +1,69 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse2, Error, Fields, ItemStruct};
+
+/// Implement `From<&Item> for Context` inserting every field under its name.
+pub fn expand_context(item: TokenStream) -> TokenStream {
+    try_expand_context(item).unwrap_or_else(|err| err.to_compile_error())
+}
+
+fn try_expand_context(item: TokenStream) -> syn::Result<TokenStream> {
+    let item: ItemStruct = parse2(item)?;
+    let name = &item.ident;
+    let Fields::Named(fields) = &item.fields else {
+        return Err(Error::new_spanned(
+            name,
+            "a struct with named fields expected",
+        ));
+    };
+    let idents: Vec<_> = fields
+        .named
+        .iter()
+        .filter_map(|f| f.ident.as_ref())
+        .collect();
+    let keys: Vec<_> = idents.iter().map(|i| i.to_string()).collect();
+
+    Ok(quote! {
+        impl<'a> From<&'a #name> for Context {
+            fn from(value: &'a #name) -> Self {
+                let mut context = Self::new();
+                #(context.insert(#keys, &value.#idents);)*
+                context
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn named_fields() {
+        let item = quote! {
+            pub struct Test {
+                pub table_name: String,
+                limit: i32,
+            }
+        };
+        let output = expand_context(item);
+        let target = quote! {
+            impl<'a> From<&'a Test> for Context {
+                fn from(value: &'a Test) -> Self {
+                    let mut context = Self::new();
+                    context.insert("table_name", &value.table_name);
+                    context.insert("limit", &value.limit);
+                    context
+                }
+            }
+        };
+        assert_eq!(output.to_string(), target.to_string());
+    }
+
+    #[test]
+    fn tuple_struct() {
+        let output = expand_context(quote! { pub struct Test(String); }).to_string();
+
+        assert!(output.contains("compile_error !"));
+    }
+}
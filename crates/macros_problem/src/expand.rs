@@ -1,37 +1,113 @@
+use crate::context::expand_context;
 use crate::item::Item;
+use crate::placeholders::{columns, placeholders};
 use convert_case::{Case, Casing};
 use macros_core::Attrs;
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use regex::Regex;
-use std::env::current_dir;
+use std::env::var;
 use std::fs::read_to_string;
-use syn::parse2;
+use std::path::{Path, PathBuf};
+use syn::{parse2, Error};
 
+/// Expand the definition, or report the error via `compile_error!`.
 pub fn expand(attrs: TokenStream, item: TokenStream) -> TokenStream {
-    let attrs: Attrs = parse2(attrs).unwrap();
+    try_expand(attrs, item).unwrap_or_else(|err| err.to_compile_error())
+}
+
+fn try_expand(attrs: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let attrs: Attrs = parse2(attrs)?;
     let client = attrs.client();
+    let severity = attrs.severity();
+    let since = match attrs.since() {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    };
 
-    let item: Item = parse2(item).unwrap();
-    let name = &item.name;
+    let item: Item = parse2(item)?;
+    let name = attrs.kind().unwrap_or(&item.name);
+    let strukt = item.item(&["Deserialize", "FromRow"]);
     let fields = item.fields();
     let limits = item.limits();
     let filters = item.filters();
 
-    let message = read_file(name, "message.txt");
-    let query = read_file(name, "query.sql");
+    let dir = templates_dir(&attrs, &item, name)?;
+    check_templates(&attrs, &item, &dir, name)?;
+    let message = template(attrs.message(), &dir, attrs.message_file(), &item)?;
+    let query = if attrs.queried() {
+        Some(template(attrs.query(), &dir, attrs.query_file(), &item)?)
+    } else {
+        None
+    };
+    let mut templates = vec![("message", message.clone())];
     let mut migration = quote! { None };
     let mut rollback = quote! { None };
     if attrs.migration() {
-        let data = read_file(name, "migration.sql");
+        let data = read_file(&dir, attrs.migration_file(), &item)?;
+        templates.push(("migration", data.clone()));
         migration = quote! { Some(#data) };
     }
     if attrs.rollback() {
-        let data = read_file(name, "rollback.sql");
+        let data = read_file(&dir, attrs.rollback_file(), &item)?;
+        templates.push(("rollback", data.clone()));
         rollback = quote! { Some(#data) };
     }
+    check_placeholders(&attrs, &item, &templates)?;
+    match &query {
+        Some(query) => {
+            check_query(&attrs, &item, query)?;
+            if attrs.check_columns() {
+                check_columns(&item, &templates, query)?;
+            }
+        }
+        None => check_no_query(&item)?,
+    }
+
+    let problem = &item.item.ident;
+    let context = expand_context(quote! { #strukt });
+    let client_type = format_ident!("{}", client);
+    let severity_type = format_ident!("{}", severity.to_case(Case::Pascal));
+    // A message-only problem is not queried, so it has no inspector
+    let inspector = match &query {
+        Some(query) => item.inspector(query)?,
+        None => quote! {},
+    };
+    let query = match &query {
+        Some(query) => quote! { Some(#query) },
+        None => quote! { None },
+    };
+
+    Ok(quote! {
+        #strukt
+
+        #context
+
+        impl CustomProblem for #problem {
+            type Client = #client_type;
+
+            fn kind_() -> &'static str {
+                #name
+            }
+            fn message_() -> &'static str {
+                #message
+            }
+            fn migration_() -> Option<&'static str> {
+                #migration
+            }
+            fn rollback_() -> Option<&'static str> {
+                #rollback
+            }
+            fn severity_() -> Severity {
+                Severity::#severity_type
+            }
+            fn applies_to_client_(client: &'static str) -> bool {
+                client == #client
+            }
+        }
+
+        #inspector
 
-    quote! {
         inventory::submit! {
             Definition {
                 client: #client,
@@ -43,21 +119,165 @@ pub fn expand(attrs: TokenStream, item: TokenStream) -> TokenStream {
                 name: #name,
                 query: #query,
                 rollback: #rollback,
+                severity: #severity,
+                since: #since,
             }
         }
+    })
+}
+
+// Ensure every placeholder of the message, migration, and rollback
+// refers to either a field of the problem, or a key listed in `context`.
+fn check_placeholders(attrs: &Attrs, item: &Item, templates: &[(&str, String)]) -> syn::Result<()> {
+    let known =
+        |var: &String| item.fields.iter().any(|f| &f.name == var) || attrs.context().contains(var);
+    for (kind, template) in templates {
+        if let Some(var) = placeholders(template).into_iter().find(|v| !known(v)) {
+            let msg = format!(
+                "The {} template of {} refers to unknown variable `{}`",
+                kind, item.name, var
+            );
+            return Err(Error::new(item.span, msg));
+        }
     }
+    Ok(())
 }
 
-fn read_file(problem: &str, filename: &'static str) -> String {
-    let path = current_dir()
-        .unwrap()
-        .join("templates")
-        .join(problem.to_case(Case::Snake))
-        .join(filename);
-    let re = Regex::new(r"[\s\n]+").unwrap();
+// Ensure every filter is selected by the query (otherwise the WHERE clause
+// built from filters refers to nothing), and every placeholder of the query
+// refers to either a filter, a limit, or a key listed in `context`.
+fn check_query(attrs: &Attrs, item: &Item, query: &str) -> syn::Result<()> {
+    for field in item.fields.iter().filter(|f| f.is_filter()) {
+        let column = field.kind.column().unwrap_or(&field.name);
+        let re = Regex::new(&format!(r"\b{}\b", regex::escape(column)))
+            .map_err(|e| Error::new(item.span, e))?;
+        if !re.is_match(query) {
+            let msg = format!(
+                "The query of {} doesn't use the filter `{}`",
+                item.name, column
+            );
+            return Err(Error::new(item.span, msg));
+        }
+    }
+    let known = |var: &String| {
+        item.fields
+            .iter()
+            .any(|f| (f.is_filter() || f.is_limit()) && &f.name == var)
+            || attrs.context().contains(var)
+    };
+    if let Some(var) = placeholders(query).into_iter().find(|v| !known(v)) {
+        let msg = format!(
+            "The query of {} refers to `{}` which is neither a filter nor a limit",
+            item.name, var
+        );
+        return Err(Error::new(item.span, msg));
+    }
+    Ok(())
+}
+
+// Ensure a problem without a query has neither filters nor limits,
+// because there is no query to apply them to.
+fn check_no_query(item: &Item) -> syn::Result<()> {
+    match item.fields.iter().find(|f| f.is_filter() || f.is_limit()) {
+        Some(field) => {
+            let msg = format!(
+                "The problem {} without a query cannot have filters or limits (`{}`)",
+                item.name, field.name
+            );
+            Err(Error::new(item.span, msg))
+        }
+        None => Ok(()),
+    }
+}
+
+// Ensure the fields used by templates are selected by the query.
+// Placeholders which are not fields (like globals) are skipped,
+// as well as the queries whose columns cannot be found out.
+fn check_columns(item: &Item, templates: &[(&str, String)], query: &str) -> syn::Result<()> {
+    let Some(columns) = columns(query) else {
+        return Ok(());
+    };
+    for (kind, template) in templates {
+        let missed = placeholders(template)
+            .into_iter()
+            .filter(|v| item.fields.iter().any(|f| &f.name == v))
+            .find(|v| !columns.contains(v));
+        if let Some(var) = missed {
+            let msg = format!(
+                "The {} template of {} uses `{}` which is never selected by the query",
+                kind, item.name, var
+            );
+            return Err(Error::new(item.span, msg));
+        }
+    }
+    Ok(())
+}
+
+// Take the inline template if provided, or read it from the file otherwise.
+fn template(inline: Option<&str>, dir: &Path, filename: &str, item: &Item) -> syn::Result<String> {
+    match inline {
+        Some(line) => Ok(squeeze(line)),
+        None => read_file(dir, filename, item),
+    }
+}
+
+// The directory with templates of the problem named after its kind.
+// It is resolved against the manifest of the crate defining the problem
+// (not the current dir), so that the expansion doesn't depend on where cargo is called from.
+fn templates_dir(attrs: &Attrs, item: &Item, kind: &str) -> syn::Result<PathBuf> {
+    let root = var("CARGO_MANIFEST_DIR").map_err(|err| {
+        Error::new(
+            item.span,
+            format!("Cannot find the crate manifest: {}", err),
+        )
+    })?;
+    Ok(PathBuf::from(root)
+        .join(attrs.templates().unwrap_or("templates"))
+        .join(kind.to_case(Case::Snake)))
+}
+
+// Ensure all the template files required by the problem exist,
+// and list the missing ones relative to the templates directory.
+fn check_templates(attrs: &Attrs, item: &Item, dir: &Path, kind: &str) -> syn::Result<()> {
+    let mut required = vec![];
+    if attrs.message().is_none() {
+        required.push(attrs.message_file());
+    }
+    if attrs.queried() && attrs.query().is_none() {
+        required.push(attrs.query_file());
+    }
+    if attrs.migration() {
+        required.push(attrs.migration_file());
+    }
+    if attrs.rollback() {
+        required.push(attrs.rollback_file());
+    }
+    let missed: Vec<_> = required
+        .into_iter()
+        .filter(|filename| !dir.join(filename).is_file())
+        .map(|filename| format!("{}/{}", kind.to_case(Case::Snake), filename))
+        .collect();
+    if missed.is_empty() {
+        return Ok(());
+    }
+    let root = attrs.templates().unwrap_or("templates");
+    let msg = format!(
+        "Missing template files in {:?}: {}",
+        root,
+        missed.join(", ")
+    );
+    Err(Error::new(item.span, msg))
+}
+
+fn read_file(dir: &Path, filename: &str, item: &Item) -> syn::Result<String> {
+    let path = dir.join(filename);
     let line = read_to_string(&path)
-        .map_err(|err| format!("Cannot read file {:?}: {}", path, err))
-        .unwrap();
+        .map_err(|err| Error::new(item.span, format!("Cannot read file {:?}: {}", path, err)))?;
+    Ok(squeeze(&line))
+}
+
+fn squeeze(line: &str) -> String {
+    let re = Regex::new(r"[\s\n]+").unwrap();
     re.replace_all(line.trim(), " ").to_string()
 }
 
@@ -78,11 +298,97 @@ mod test {
         }
     }
 
+    // Implementations generated for the `Test` problem
+    fn companions(
+        message: TokenStream,
+        migration: TokenStream,
+        rollback: TokenStream,
+        query: TokenStream,
+    ) -> TokenStream {
+        quote! {
+            impl<'a> From<&'a Test> for Context {
+                fn from(value: &'a Test) -> Self {
+                    let mut context = Self::new();
+                    context.insert("table_name", &value.table_name);
+                    context.insert("column_name", &value.column_name);
+                    context.insert("max_size", &value.max_size);
+                    context
+                }
+            }
+
+            impl CustomProblem for Test {
+                type Client = PostgresClient;
+
+                fn kind_() -> &'static str {
+                    "Test"
+                }
+                fn message_() -> &'static str {
+                    #message
+                }
+                fn migration_() -> Option<&'static str> {
+                    #migration
+                }
+                fn rollback_() -> Option<&'static str> {
+                    #rollback
+                }
+                fn severity_() -> Severity {
+                    Severity::Error
+                }
+                fn applies_to_client_(client: &'static str) -> bool {
+                    client == "PostgresClient"
+                }
+            }
+
+            #[derive(Debug, Deserialize, Serialize)]
+            pub struct TestFilter {
+                pub table_name: Option<String>,
+            }
+
+            #[derive(Debug, Deserialize, Serialize)]
+            pub struct TestInspector {
+                pub max_size: i32,
+                pub only: Option<Vec<TestFilter>>,
+                pub except: Option<Vec<TestFilter>>,
+            }
+
+            impl<'a> From<&'a TestInspector> for Context {
+                fn from(value: &'a TestInspector) -> Self {
+                    let mut context = Self::new();
+                    context.insert("max_size", &value.max_size);
+                    context
+                }
+            }
+
+            impl CustomInspector for TestInspector {
+                type Problem = Test;
+
+                fn query_() -> &'static str {
+                    #query
+                }
+            }
+        }
+    }
+
     #[test]
     fn default() {
         let attrs = quote! {};
-        let output = expand(attrs.into(), item().into());
+        let output = expand(attrs, item());
+        let companions = companions(
+            quote! { "./message.txt" },
+            quote! { Some("./migration.sql") },
+            quote! { Some("./rollback.sql") },
+            quote! { "SELECT table_name FROM ./query.sql" },
+        );
         let target = quote! {
+            #[derive(Deserialize, FromRow)]
+            pub struct Test {
+                pub table_name: String,
+                pub column_name: String,
+                pub max_size: i32,
+            }
+
+            #companions
+
             inventory::submit! {
                 Definition {
                     client: "PostgresClient",
@@ -95,21 +401,29 @@ mod test {
                         TaggedField {
                             name: "table_name",
                             ty: "Option < String >",
-                            desc: "Table name"
+                            desc: "Table name",
+                            default: None,
+                            column: None,
+                            op: None
                         },
                     ],
                     limits: &[
                         TaggedField {
                             name: "max_size",
                             ty: "i32",
-                            desc: "Max size of the column"
+                            desc: "Max size of the column",
+                            default: None,
+                            column: None,
+                            op: None
                         },
                     ],
                     message: "./message.txt",
                     migration: Some("./migration.sql"),
                     name: "Test",
-                    query: "./query.sql",
+                    query: Some("SELECT table_name FROM ./query.sql"),
                     rollback: Some("./rollback.sql"),
+                    severity: "error",
+                    since: None,
                 }
             }
         };
@@ -119,8 +433,23 @@ mod test {
     #[test]
     fn without_rollback() {
         let attrs = quote! { rollback = false };
-        let output = expand(attrs.into(), item().into());
+        let output = expand(attrs, item());
+        let companions = companions(
+            quote! { "./message.txt" },
+            quote! { Some("./migration.sql") },
+            quote! { None },
+            quote! { "SELECT table_name FROM ./query.sql" },
+        );
         let target = quote! {
+            #[derive(Deserialize, FromRow)]
+            pub struct Test {
+                pub table_name: String,
+                pub column_name: String,
+                pub max_size: i32,
+            }
+
+            #companions
+
             inventory::submit! {
                 Definition {
                     client: "PostgresClient",
@@ -133,21 +462,29 @@ mod test {
                         TaggedField {
                             name: "table_name",
                             ty: "Option < String >",
-                            desc: "Table name"
+                            desc: "Table name",
+                            default: None,
+                            column: None,
+                            op: None
                         },
                     ],
                     limits: &[
                         TaggedField {
                             name: "max_size",
                             ty: "i32",
-                            desc: "Max size of the column"
+                            desc: "Max size of the column",
+                            default: None,
+                            column: None,
+                            op: None
                         },
                     ],
                     message: "./message.txt",
                     migration: Some("./migration.sql"),
                     name: "Test",
-                    query: "./query.sql",
+                    query: Some("SELECT table_name FROM ./query.sql"),
                     rollback: None,
+                    severity: "error",
+                    since: None,
                 }
             }
         };
@@ -157,8 +494,23 @@ mod test {
     #[test]
     fn without_migration() {
         let attrs = quote! { migration = false };
-        let output = expand(attrs.into(), item().into());
+        let output = expand(attrs, item());
+        let companions = companions(
+            quote! { "./message.txt" },
+            quote! { None },
+            quote! { None },
+            quote! { "SELECT table_name FROM ./query.sql" },
+        );
         let target = quote! {
+            #[derive(Deserialize, FromRow)]
+            pub struct Test {
+                pub table_name: String,
+                pub column_name: String,
+                pub max_size: i32,
+            }
+
+            #companions
+
             inventory::submit! {
                 Definition {
                     client: "PostgresClient",
@@ -171,24 +523,312 @@ mod test {
                         TaggedField {
                             name: "table_name",
                             ty: "Option < String >",
-                            desc: "Table name"
+                            desc: "Table name",
+                            default: None,
+                            column: None,
+                            op: None
                         },
                     ],
                     limits: &[
                         TaggedField {
                             name: "max_size",
                             ty: "i32",
-                            desc: "Max size of the column"
+                            desc: "Max size of the column",
+                            default: None,
+                            column: None,
+                            op: None
                         },
                     ],
                     message: "./message.txt",
                     migration: None,
                     name: "Test",
-                    query: "./query.sql",
+                    query: Some("SELECT table_name FROM ./query.sql"),
                     rollback: None,
+                    severity: "error",
+                    since: None,
                 }
             }
         };
         assert_eq!(output.to_string(), target.to_string());
     }
+
+    #[test]
+    fn inline_templates() {
+        let attrs = quote! {
+            message = "Column {{ column_name }}\n  is too long",
+            query = "SELECT table_name FROM tables",
+        };
+        let output = expand(attrs, item());
+        let companions = companions(
+            quote! { "Column {{ column_name }} is too long" },
+            quote! { Some("./migration.sql") },
+            quote! { Some("./rollback.sql") },
+            quote! { "SELECT table_name FROM tables" },
+        );
+        let target = quote! {
+            #[derive(Deserialize, FromRow)]
+            pub struct Test {
+                pub table_name: String,
+                pub column_name: String,
+                pub max_size: i32,
+            }
+
+            #companions
+
+            inventory::submit! {
+                Definition {
+                    client: "PostgresClient",
+                    fields: &[
+                        Field { name: "table_name", ty: "String" },
+                        Field { name: "column_name", ty: "String" },
+                        Field { name: "max_size", ty: "i32" },
+                    ],
+                    filters: &[
+                        TaggedField {
+                            name: "table_name",
+                            ty: "Option < String >",
+                            desc: "Table name",
+                            default: None,
+                            column: None,
+                            op: None
+                        },
+                    ],
+                    limits: &[
+                        TaggedField {
+                            name: "max_size",
+                            ty: "i32",
+                            desc: "Max size of the column",
+                            default: None,
+                            column: None,
+                            op: None
+                        },
+                    ],
+                    message: "Column {{ column_name }} is too long",
+                    migration: Some("./migration.sql"),
+                    name: "Test",
+                    query: Some("SELECT table_name FROM tables"),
+                    rollback: Some("./rollback.sql"),
+                    severity: "error",
+                    since: None,
+                }
+            }
+        };
+        assert_eq!(output.to_string(), target.to_string());
+    }
+
+    #[test]
+    fn unknown_placeholder() {
+        let attrs = quote! { message = "Table {{ tabel_name }}" };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(output.contains("compile_error !"));
+        assert!(
+            output.contains("The message template of Test refers to unknown variable `tabel_name`")
+        );
+    }
+
+    #[test]
+    fn placeholder_from_context() {
+        let attrs =
+            quote! { message = "Table {{ schema }}.{{ table_name }}", context = ["schema"] };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(output.contains("inventory :: submit !"));
+    }
+
+    #[test]
+    fn severity() {
+        for severity in ["info", "warning", "error"] {
+            let attrs = quote! { severity = #severity };
+            let output = expand(attrs, item()).to_string();
+
+            assert!(output.contains(&format!("severity : \"{}\"", severity)));
+        }
+    }
+
+    #[test]
+    fn since() {
+        let attrs = quote! { since = "1.2.0" };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(output.contains("since : Some (\"1.2.0\")"));
+    }
+
+    #[test]
+    fn unknown_severity() {
+        let attrs = quote! { severity = "fatal" };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(output.contains("compile_error !"));
+        assert!(output.contains("Unknown severity"));
+    }
+
+    #[test]
+    fn missed_template() {
+        let attrs = quote! { templates = "templates/missed" };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(output.contains("compile_error !"));
+        assert!(output.contains("Missing template files in \\\"templates/missed\\\""));
+        assert!(output.contains("test/message.txt, test/query.sql"));
+    }
+
+    #[test]
+    fn missed_rollback_template() {
+        let attrs = quote! { rollback_file = "missed.sql" };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(output.contains("compile_error !"));
+        assert!(output.contains("Missing template files in \\\"templates\\\": test/missed.sql"));
+    }
+
+    #[test]
+    fn skip_check_of_disabled_templates() {
+        let attrs = quote! { migration = false, rollback_file = "missed.sql" };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(!output.contains("compile_error !"));
+    }
+
+    #[test]
+    fn malformed_item() {
+        let output = expand(quote! {}, quote! { pub fn test() {} }).to_string();
+
+        assert!(output.contains("compile_error !"));
+    }
+
+    #[test]
+    fn custom_templates() {
+        let attrs = quote! { templates = "templates/custom" };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(output.contains(&quote! { message: "./custom/message.txt", }.to_string()));
+        assert!(output.contains(
+            &quote! { query: Some("SELECT table_name FROM ./custom/query.sql"), }.to_string()
+        ));
+        assert!(output.contains(&quote! { migration: Some("./custom/migration.sql"), }.to_string()));
+        assert!(output.contains(&quote! { rollback: Some("./custom/rollback.sql"), }.to_string()));
+    }
+
+    // The current dir is global for all tests running in parallel,
+    // hence the test runs the expansion in a separate process.
+    #[test]
+    fn independent_from_current_dir() {
+        let test = "expand::test::expand_in_current_dir";
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([test, "--exact", "--ignored"])
+            .current_dir(std::env::temp_dir())
+            .output()
+            .unwrap();
+
+        assert!(output.status.success(), "{:?}", output);
+        assert!(String::from_utf8_lossy(&output.stdout).contains("1 passed"));
+    }
+
+    #[test]
+    #[ignore = "runs from another dir by independent_from_current_dir"]
+    fn expand_in_current_dir() {
+        let output = expand(quote! {}, item()).to_string();
+
+        assert!(output.contains(&quote! { message: "./message.txt", }.to_string()));
+    }
+
+    #[test]
+    fn unused_filter() {
+        let attrs = quote! { query = "SELECT column_name FROM tables" };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(output.contains("compile_error !"));
+        assert!(output.contains("The query of Test doesn't use the filter `table_name`"));
+    }
+
+    #[test]
+    fn undefined_query_placeholder() {
+        let attrs = quote! { query = "SELECT table_name FROM tables LIMIT {{ max_rows }}" };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(output.contains("compile_error !"));
+        assert!(output.contains(
+            "The query of Test refers to `max_rows` which is neither a filter nor a limit"
+        ));
+    }
+
+    #[test]
+    fn custom_kind() {
+        let attrs = quote! { kind = "Test" };
+        let item = quote! {
+            pub struct Renamed {
+                #[filter("Table name")]
+                pub table_name: String,
+            }
+        };
+        let output = expand(attrs, item).to_string();
+
+        assert!(output.contains(&quote! { name: "Test", }.to_string()));
+        assert!(output.contains(&quote! { message: "./message.txt", }.to_string()));
+    }
+
+    #[test]
+    fn column_not_selected() {
+        let attrs = quote! {
+            check_columns = true,
+            message = "Column {{ column_name }} of {{ table_name }}",
+            query = "SELECT t.relname AS table_name FROM pg_class t",
+        };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(output.contains("compile_error !"));
+        assert!(output.contains(
+            "The message template of Test uses `column_name` which is never selected by the query"
+        ));
+    }
+
+    #[test]
+    fn column_check_disabled() {
+        let attrs = quote! {
+            message = "Column {{ column_name }} of {{ table_name }}",
+            query = "SELECT t.relname AS table_name FROM pg_class t",
+        };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(!output.contains("compile_error !"));
+    }
+
+    #[test]
+    fn custom_template_files() {
+        let attrs = quote! { message_file = "msg.md", query_file = "q.sql" };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(output.contains(&quote! { message: "./msg.md", }.to_string()));
+        assert!(
+            output.contains(&quote! { query: Some("SELECT table_name FROM ./q.sql"), }.to_string())
+        );
+        assert!(output.contains(&quote! { migration: Some("./migration.sql"), }.to_string()));
+    }
+
+    #[test]
+    fn without_query() {
+        let attrs = quote! { query = false, query_file = "missed.sql" };
+        let item = quote! {
+            pub struct Test {
+                pub table_name: String,
+            }
+        };
+        let output = expand(attrs, item).to_string();
+
+        assert!(!output.contains("compile_error !"));
+        assert!(output.contains(&quote! { query: None, }.to_string()));
+        assert!(!output.contains("TestInspector"));
+    }
+
+    #[test]
+    fn filters_without_query() {
+        let attrs = quote! { query = false };
+        let output = expand(attrs, item()).to_string();
+
+        assert!(output.contains("compile_error !"));
+        assert!(output.contains(
+            "The problem Test without a query cannot have filters or limits (`table_name`)"
+        ));
+    }
 }
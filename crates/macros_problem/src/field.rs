@@ -1,10 +1,13 @@
+use macros_core::OPERATORS;
 use quote::quote;
-use syn::{Attribute, Error, Expr, ExprLit, Lit};
+use syn::{punctuated::Punctuated, Attribute, Error, Expr, ExprLit, Lit, Meta, Token};
 
 /// Convert a `syn::Field` into a `Field` struct
 /// accepting nor more than one of the following attributes:
-/// `#[limit("description")]`,
-/// `#[filter("description")]`.
+/// `#[limit("description")]` (or `#[limit("description", default = 255)]`),
+/// `#[filter("description")]` (or `#[filter("description", column = "relname", op = ">=")]`).
+/// When the description is omitted (`#[filter]`),
+/// the doc comment of the field is used instead.
 #[derive(Debug, PartialEq)]
 pub struct Field {
     pub kind: Kind,
@@ -15,11 +18,11 @@ pub struct Field {
 
 impl Field {
     pub fn is_limit(&self) -> bool {
-        matches!(self.kind, Kind::Limit(_))
+        matches!(self.kind, Kind::Limit(..))
     }
 
     pub fn is_filter(&self) -> bool {
-        matches!(self.kind, Kind::Filter(_))
+        matches!(self.kind, Kind::Filter(..))
     }
 }
 
@@ -41,20 +44,42 @@ impl TryFrom<&syn::Field> for Field {
 }
 
 // Extract the kind of the field along with its description
+// (and the default value of the limit, or the column and operator of the filter if provided)
 #[derive(Debug, PartialEq)]
 pub enum Kind {
-    Filter(String),
-    Limit(String),
+    Filter(String, Option<String>, Option<String>),
+    Limit(String, Option<String>),
     Plain,
 }
 
 impl Kind {
     pub fn desc(&self) -> &str {
         match self {
-            Kind::Filter(desc) | Kind::Limit(desc) => desc,
+            Kind::Filter(desc, ..) | Kind::Limit(desc, _) => desc,
             Kind::Plain => "",
         }
     }
+
+    pub fn column(&self) -> Option<&str> {
+        match self {
+            Kind::Filter(_, column, _) => column.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn op(&self) -> Option<&str> {
+        match self {
+            Kind::Filter(_, _, op) => op.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn default(&self) -> Option<&str> {
+        match self {
+            Kind::Limit(_, default) => default.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 impl TryFrom<&syn::Field> for Kind {
@@ -75,7 +100,15 @@ impl TryFrom<&syn::Field> for Kind {
                 }
             }
         }
-        Ok(kind)
+        let doc =
+            || doc(&value.attrs).ok_or_else(|| Error::new_spanned(value, "a description missed"));
+        match kind {
+            Kind::Filter(desc, column, op) if desc.is_empty() => {
+                Ok(Kind::Filter(doc()?, column, op))
+            }
+            Kind::Limit(desc, default) if desc.is_empty() => Ok(Kind::Limit(doc()?, default)),
+            kind => Ok(kind),
+        }
     }
 }
 
@@ -84,14 +117,42 @@ impl TryFrom<&Attribute> for Kind {
 
     fn try_from(value: &Attribute) -> Result<Self, Self::Error> {
         match value.path().get_ident() {
-            Some(ident) if ident == "limit" => Ok(Self::Limit(desc(value)?)),
-            Some(ident) if ident == "filter" => Ok(Self::Filter(desc(value)?)),
+            Some(ident) if ident == "limit" => {
+                let args = Args::parse(value, &["default"])?;
+                let default = args.option("default");
+                Ok(Self::Limit(args.desc, default))
+            }
+            Some(ident) if ident == "filter" => {
+                let args = Args::parse(value, &["column", "op"])?;
+                let column = args.str_option("column")?;
+                if let Some(column) = column.as_ref().filter(|c| !is_identifier(c)) {
+                    let msg = format!("the column {:?} is not a plain identifier", column);
+                    return Err(Error::new_spanned(value, msg));
+                }
+                let op = args.str_option("op")?.map(|op| op.to_uppercase());
+                if let Some(op) = op.as_ref().filter(|op| !OPERATORS.contains(&op.as_str())) {
+                    let msg = format!("unknown operator {:?}, use one of {:?}", op, OPERATORS);
+                    return Err(Error::new_spanned(value, msg));
+                }
+                Ok(Self::Filter(args.desc, column, op))
+            }
+            Some(ident) if ident == "doc" => Ok(Self::Plain),
             Some(_) => Err(Error::new_spanned(value, "unknown attribute")),
             None => Ok(Self::Plain),
         }
     }
 }
 
+// Check that the column of a filter is a plain SQL identifier, because
+// the name is both interpolated into the WHERE clause and looked up in the query.
+fn is_identifier(column: &str) -> bool {
+    let mut chars = column.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 // Extract the name of the field
 struct Name(String);
 
@@ -140,15 +201,85 @@ impl TryFrom<&syn::Field> for OptionalType {
     }
 }
 
-fn desc(value: &Attribute) -> Result<String, Error> {
-    if let Expr::Lit(ExprLit {
-        lit: Lit::Str(s), ..
-    }) = &value.parse_args()?
-    {
-        Ok(s.value())
-    } else {
-        Err(Error::new_spanned(value, "a description missed"))
+// Arguments of the attribute: the description followed by
+// the named options like `#[limit("description", default = 255)]`.
+// The description of a bare attribute (`#[filter]`) is empty
+// to be taken from the doc comment later.
+struct Args {
+    desc: String,
+    options: Vec<(String, Expr)>,
+}
+
+impl Args {
+    fn parse(value: &Attribute, allowed: &[&str]) -> Result<Self, Error> {
+        let mut output = Self {
+            desc: String::new(),
+            options: vec![],
+        };
+        if let Meta::Path(_) = value.meta {
+            return Ok(output);
+        }
+        let args = value.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+        for (i, arg) in args.iter().enumerate() {
+            match arg {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }) if i == 0 => output.desc = s.value(),
+                Expr::Assign(a) => {
+                    let left = &a.left;
+                    let right = &a.right;
+                    let key = quote! { #left }.to_string();
+                    if !allowed.contains(&key.as_str()) {
+                        return Err(Error::new_spanned(arg, "unknown argument"));
+                    }
+                    output.options.push((key, (**right).clone()));
+                }
+                _ => return Err(Error::new_spanned(value, "a description missed")),
+            }
+        }
+        Ok(output)
+    }
+
+    // The option as it is written (like `255`)
+    fn option(&self, key: &str) -> Option<String> {
+        self.options
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| quote! { #v }.to_string())
     }
+
+    // The value of the string option (like `"relname"`)
+    fn str_option(&self, key: &str) -> Result<Option<String>, Error> {
+        match self.options.iter().find(|(k, _)| k == key) {
+            Some((
+                _,
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }),
+            )) => Ok(Some(s.value())),
+            Some((_, v)) => Err(Error::new_spanned(v, "a string expected")),
+            None => Ok(None),
+        }
+    }
+}
+
+// Join lines of the doc comment
+fn doc(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta {
+            Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }) => Some(s.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+    (!lines.is_empty()).then(|| lines.join(" "))
 }
 
 #[cfg(test)]
@@ -209,7 +340,7 @@ mod test {
         assert_eq!(
             output,
             Field {
-                kind: Kind::Filter("name description".to_string()),
+                kind: Kind::Filter("name description".to_string(), None, None),
                 name: "name".to_string(),
                 optional_ty: "Option < String >".to_string(),
                 ty: "String".to_string(),
@@ -228,7 +359,7 @@ mod test {
         assert_eq!(
             output,
             Field {
-                kind: Kind::Limit("name description".to_string()),
+                kind: Kind::Limit("name description".to_string(), None),
                 name: "name".to_string(),
                 optional_ty: "Option < String >".to_string(),
                 ty: "String".to_string(),
@@ -236,6 +367,148 @@ mod test {
         );
     }
 
+    #[test]
+    fn doc_derived_description() {
+        let input = field(quote! {
+            /// The name
+            /// of the table
+            #[filter]
+            pub name: String,
+        });
+        let output: Field = (&input).try_into().unwrap();
+
+        assert_eq!(
+            output.kind,
+            Kind::Filter("The name of the table".to_string(), None, None)
+        );
+    }
+
+    #[test]
+    fn explicit_description_over_doc() {
+        let input = field(quote! {
+            /// The doc comment
+            #[limit("name description")]
+            pub name: String,
+        });
+        let output: Field = (&input).try_into().unwrap();
+
+        assert_eq!(
+            output.kind,
+            Kind::Limit("name description".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn documented_plain() {
+        let input = field(quote! {
+            /// The doc comment
+            pub name: String,
+        });
+        let output: Field = (&input).try_into().unwrap();
+
+        assert_eq!(output.kind, Kind::Plain);
+    }
+
+    #[test]
+    fn limit_with_default() {
+        let input = field(quote! {
+            #[limit("name description", default = 255)]
+            pub name: u32,
+        });
+        let output: Field = (&input).try_into().unwrap();
+
+        assert_eq!(
+            output.kind,
+            Kind::Limit("name description".to_string(), Some("255".to_string()))
+        );
+        assert_eq!(output.kind.default(), Some("255"));
+    }
+
+    #[test]
+    fn doc_derived_limit_with_default() {
+        let input = field(quote! {
+            /// The doc comment
+            #[limit(default = 255)]
+            pub name: u32,
+        });
+        let output: Field = (&input).try_into().unwrap();
+
+        assert_eq!(
+            output.kind,
+            Kind::Limit("The doc comment".to_string(), Some("255".to_string()))
+        );
+    }
+
+    #[test]
+    fn filter_with_column() {
+        let input = field(quote! {
+            #[filter("name description", column = "nspname")]
+            pub scope_name: String,
+        });
+        let output: Field = (&input).try_into().unwrap();
+
+        assert_eq!(
+            output.kind,
+            Kind::Filter(
+                "name description".to_string(),
+                Some("nspname".to_string()),
+                None
+            )
+        );
+        assert_eq!(output.kind.column(), Some("nspname"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn filter_with_expression_column() {
+        let input = field(quote! {
+            #[filter("name description", column = "pg_relation_size(oid)")]
+            pub size: i64,
+        });
+        let _: Field = (&input).try_into().unwrap();
+    }
+
+    #[test]
+    fn filter_with_operator() {
+        let input = field(quote! {
+            #[filter("name description", op = "not like")]
+            pub table_name: String,
+        });
+        let output: Field = (&input).try_into().unwrap();
+
+        assert_eq!(output.kind.op(), Some("NOT LIKE"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn filter_with_unknown_operator() {
+        let input = field(quote! {
+            #[filter("name description", op = "; DROP TABLE")]
+            pub table_name: String,
+        });
+        let _: Field = (&input).try_into().unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn filter_with_non_string_column() {
+        let input = field(quote! {
+            #[filter("name description", column = 42)]
+            pub scope_name: String,
+        });
+        let _: Field = (&input).try_into().unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn filter_with_default() {
+        let input = field(quote! {
+            #[filter("name description", default = "users")]
+            pub name: String,
+        });
+        let _: Field = (&input).try_into().unwrap();
+    }
+
     #[test]
     #[should_panic]
     fn limit_without_description() {
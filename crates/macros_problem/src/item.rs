@@ -1,17 +1,120 @@
 use crate::field::Field;
-use proc_macro2::TokenStream;
-use quote::quote;
-use syn::parse::{Parse, ParseStream};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_str,
+    punctuated::Punctuated,
+    Error, Fields, ItemStruct, Path, Token, Type,
+};
 
 /// Parse the struct definition of the problem with field attributes
 /// `#[limit("description")]`,
 /// `#[filter("description")]`
+/// The struct itself is kept without these attributes.
 pub struct Item {
     pub name: String,
     pub fields: Vec<Field>,
+    pub span: Span,
+    pub item: ItemStruct,
 }
 
 impl Item {
+    /// The struct with derives added unless the user provided them already.
+    pub fn item(&self, derives: &[&str]) -> TokenStream {
+        let item = &self.item;
+        let derived: Vec<String> = item
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("derive"))
+            .filter_map(|a| {
+                a.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+                    .ok()
+            })
+            .flat_map(|list| list.into_iter())
+            .filter_map(|p| p.segments.last().map(|s| s.ident.to_string()))
+            .collect();
+        let missed: Vec<_> = derives
+            .iter()
+            .filter(|d| !derived.iter().any(|x| x == *d))
+            .map(|d| Ident::new(d, Span::call_site()))
+            .collect();
+        if missed.is_empty() {
+            quote! { #item }
+        } else {
+            quote! {
+                #[derive(#(#missed),*)]
+                #item
+            }
+        }
+    }
+
+    /// The companion inspector with limits, and `only`/`except` lists of filters,
+    /// named after the problem: `<Name>Filter` and `<Name>Inspector`.
+    pub fn inspector(&self, query: &str) -> syn::Result<TokenStream> {
+        let problem = &self.item.ident;
+        let vis = &self.item.vis;
+        let filter = format_ident!("{}Filter", problem);
+        let inspector = format_ident!("{}Inspector", problem);
+
+        let mut filters = vec![];
+        let mut filter_types = vec![];
+        let mut filter_attrs = vec![];
+        let mut limits = vec![];
+        let mut limit_types = vec![];
+        for field in self.fields.iter() {
+            if field.is_filter() {
+                filters.push(format_ident!("{}", field.name));
+                filter_types.push(parse_str::<Type>(&field.optional_ty)?);
+                // The column (followed by the operator) is used in the WHERE clause,
+                // while the config uses the field name
+                let column = field.kind.column().unwrap_or(&field.name);
+                filter_attrs.push(match (field.kind.column(), field.kind.op()) {
+                    (None, None) => quote! {},
+                    (_, None) => quote! { #[serde(rename(serialize = #column))] },
+                    (_, Some(op)) => {
+                        let key = format!("{} {}", column, op);
+                        quote! { #[serde(rename(serialize = #key))] }
+                    }
+                });
+            } else if field.is_limit() {
+                limits.push(format_ident!("{}", field.name));
+                limit_types.push(parse_str::<Type>(&field.ty)?);
+            }
+        }
+        let keys: Vec<_> = limits.iter().map(|l| l.to_string()).collect();
+
+        Ok(quote! {
+            #[derive(Debug, Deserialize, Serialize)]
+            #vis struct #filter {
+                #(#filter_attrs pub #filters: #filter_types,)*
+            }
+
+            #[derive(Debug, Deserialize, Serialize)]
+            #vis struct #inspector {
+                #(pub #limits: #limit_types,)*
+                pub only: Option<Vec<#filter>>,
+                pub except: Option<Vec<#filter>>,
+            }
+
+            impl<'a> From<&'a #inspector> for Context {
+                fn from(value: &'a #inspector) -> Self {
+                    let mut context = Self::new();
+                    #(context.insert(#keys, &value.#limits);)*
+                    context
+                }
+            }
+
+            impl CustomInspector for #inspector {
+                type Problem = #problem;
+
+                fn query_() -> &'static str {
+                    #query
+                }
+            }
+        })
+    }
+
     pub fn fields(&self) -> TokenStream {
         let list: TokenStream = self
             .fields
@@ -26,8 +129,12 @@ impl Item {
             .fields
             .iter()
             .filter(|f| f.is_filter())
-            .map(|f| (&f.name, &f.optional_ty, f.kind.desc()))
-            .map(|(name, ty, desc)| quote! { TaggedField { name: #name, ty: #ty, desc: #desc }, })
+            .map(|f| {
+                let (name, ty, desc) = (&f.name, &f.optional_ty, f.kind.desc());
+                let column = optional(f.kind.column());
+                let op = optional(f.kind.op());
+                quote! { TaggedField { name: #name, ty: #ty, desc: #desc, default: None, column: #column, op: #op }, }
+            })
             .collect();
         quote! { &[#list] }
     }
@@ -37,22 +144,52 @@ impl Item {
             .fields
             .iter()
             .filter(|f| f.is_limit())
-            .map(|f| (&f.name, &f.ty, f.kind.desc()))
-            .map(|(name, ty, desc)| quote! { TaggedField { name: #name, ty: #ty, desc: #desc }, })
+            .map(|f| (&f.name, &f.ty, f.kind.desc(), f.kind.default()))
+            .map(|(name, ty, desc, default)| {
+                let default = match default {
+                    Some(value) => quote! { Some(#value) },
+                    None => quote! { None },
+                };
+                quote! { TaggedField { name: #name, ty: #ty, desc: #desc, default: #default, column: None, op: None }, }
+            })
             .collect();
         quote! { &[#list] }
     }
 }
 
+fn optional(value: Option<&str>) -> TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
 impl Parse for Item {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let item = input.parse::<syn::ItemStruct>()?;
+        let mut item = input.parse::<ItemStruct>()?;
+        if !matches!(item.fields, Fields::Named(_)) {
+            return Err(Error::new_spanned(
+                &item.ident,
+                "#[problem] requires a struct with named fields",
+            ));
+        }
         let name = item.ident.to_string();
+        let span = item.ident.span();
         let mut fields = Vec::with_capacity(item.fields.len());
         for (i, field) in item.fields.iter().enumerate() {
             fields.insert(i, field.try_into()?);
         }
-        Ok(Self { name, fields })
+        for field in item.fields.iter_mut() {
+            field
+                .attrs
+                .retain(|a| !a.path().is_ident("filter") && !a.path().is_ident("limit"));
+        }
+        Ok(Self {
+            name,
+            fields,
+            span,
+            item,
+        })
     }
 }
 
@@ -79,6 +216,77 @@ mod test {
         assert_eq!(output.name, "Foo");
     }
 
+    #[test]
+    fn item_with_derives() {
+        let input = parse_quote! {
+            #[derive(Debug, serde::Deserialize)]
+            pub struct Foo {
+                /// The a field
+                #[limit("the a message")]
+                pub a: i32,
+            }
+        };
+        let output = parse2::<Item>(input)
+            .unwrap()
+            .item(&["Deserialize", "FromRow"]);
+        let target = quote! {
+            #[derive(FromRow)]
+            #[derive(Debug, serde::Deserialize)]
+            pub struct Foo {
+                /// The a field
+                pub a: i32,
+            }
+        };
+
+        assert_eq!(output.to_string(), target.to_string());
+    }
+
+    #[test]
+    fn filter_with_operator() {
+        let input = parse_quote! {
+            pub struct Foo {
+                #[filter("the size", column = "relpages", op = ">=")]
+                pub size: i64,
+                #[filter("the name", op = "like")]
+                pub name: String,
+            }
+        };
+        let output = parse2::<Item>(input)
+            .unwrap()
+            .inspector("SELECT 1")
+            .unwrap()
+            .to_string();
+
+        assert!(output.contains(r#"rename (serialize = "relpages >=")"#));
+        assert!(output.contains(r#"rename (serialize = "name LIKE")"#));
+    }
+
+    #[test]
+    fn tuple_struct() {
+        let input = parse_quote! {
+            pub struct Foo(String, i32);
+        };
+        let error = parse2::<Item>(input).err().unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "#[problem] requires a struct with named fields"
+        );
+    }
+
+    #[test]
+    fn unit_struct() {
+        let input = parse_quote! {
+            pub struct Foo;
+        };
+        let error = parse2::<Item>(input).err().unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "#[problem] requires a struct with named fields"
+        );
+    }
+
     #[test]
     #[should_panic]
     fn not_a_struct() {
@@ -1,7 +1,10 @@
+mod context;
 mod expand;
 mod field;
 mod item;
+mod placeholders;
 
+use context::expand_context;
 use expand::expand;
 use proc_macro::TokenStream;
 
@@ -9,3 +12,8 @@ use proc_macro::TokenStream;
 pub fn problem(attr: TokenStream, item: TokenStream) -> TokenStream {
     expand(attr.into(), item.into()).into()
 }
+
+#[proc_macro_derive(IntoContext)]
+pub fn into_context(item: TokenStream) -> TokenStream {
+    expand_context(item.into()).into()
+}
@@ -0,0 +1,107 @@
+use regex::Regex;
+
+/// Extract names of the variables used by `{{ ... }}` placeholders of a template.
+///
+/// Only the head of the expression is taken (`{{ name | upper }}` -> `name`,
+/// `{{ table.name }}` -> `table`), while function calls like `{{ now() }}`
+/// and literals are skipped.
+pub fn placeholders(template: &str) -> Vec<String> {
+    let re = Regex::new(r"\{\{-?\s*([A-Za-z_][A-Za-z0-9_]*)\s*(\()?").unwrap();
+    let mut output: Vec<String> = Vec::new();
+    for cap in re.captures_iter(template) {
+        let name = cap[1].to_string();
+        if cap.get(2).is_none() && !output.contains(&name) {
+            output.push(name);
+        }
+    }
+    output
+}
+
+/// Extract names of the columns selected by the query when they are statically discernible.
+///
+/// Only the list between the leading `SELECT` and the first `FROM` is analyzed.
+/// Every column is named either by its alias (`... AS name`), or by its last identifier
+/// (`t.name` -> `name`). The result is `None` for `SELECT *`, or when the query
+/// doesn't start with `SELECT` (like CTE), because the columns cannot be found out then.
+pub fn columns(query: &str) -> Option<Vec<String>> {
+    let select = Regex::new(r"(?is)^\s*SELECT\s+(?:DISTINCT\s+)?(.*?)\s+FROM\s").unwrap();
+    let alias = Regex::new(r#"(?i)\bAS\s+"?([A-Za-z_][A-Za-z0-9_]*)"?\s*$"#).unwrap();
+    let last = Regex::new(r#"([A-Za-z_][A-Za-z0-9_]*)"?\s*$"#).unwrap();
+
+    let list = select.captures(query)?.get(1)?.as_str();
+    let mut output = vec![];
+    for item in split(list) {
+        if item.trim().ends_with('*') {
+            return None;
+        }
+        if let Some(cap) = alias.captures(&item).or_else(|| last.captures(&item)) {
+            output.push(cap[1].to_string());
+        }
+    }
+    Some(output)
+}
+
+// Split the list by commas outside of parentheses
+fn split(list: &str) -> Vec<String> {
+    let mut output = vec![String::new()];
+    let mut depth = 0;
+    for c in list.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                output.push(String::new());
+                continue;
+            }
+            _ => (),
+        }
+        output.last_mut().unwrap().push(c);
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn variables() {
+        let output = placeholders("{{ scope_name }}.{{table_name}} {{- scope_name -}}");
+
+        assert_eq!(output, ["scope_name", "table_name"]);
+    }
+
+    #[test]
+    fn filters_and_attributes() {
+        let output = placeholders("{{ name | upper }} {{ table.name }}");
+
+        assert_eq!(output, ["name", "table"]);
+    }
+
+    #[test]
+    fn selected_columns() {
+        let query = "SELECT t.relnamespace::regnamespace AS scope_name, \
+                            a.attname, \"column_name\", COALESCE(a.x, 1) AS x, \
+                            {{ limit }} AS limit \
+                     FROM pg_attribute a";
+
+        assert_eq!(
+            columns(query).unwrap(),
+            ["scope_name", "attname", "column_name", "x", "limit"]
+        );
+    }
+
+    #[test]
+    fn undiscernible_columns() {
+        assert!(columns("SELECT * FROM pg_class").is_none());
+        assert!(columns("SELECT c.* FROM pg_class c").is_none());
+        assert!(columns("WITH t AS (SELECT 1) SELECT a FROM t").is_none());
+    }
+
+    #[test]
+    fn functions_and_literals() {
+        let output = placeholders("{{ now() }} {{ \"text\" }} {{ 42 }}");
+
+        assert!(output.is_empty());
+    }
+}
@@ -0,0 +1 @@
+./custom/migration.sql
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT table_name FROM ./custom/query.sql
\ No newline at end of file
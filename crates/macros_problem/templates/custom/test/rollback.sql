@@ -0,0 +1 @@
+./custom/rollback.sql
\ No newline at end of file
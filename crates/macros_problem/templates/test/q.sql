@@ -0,0 +1 @@
+SELECT table_name FROM ./q.sql
\ No newline at end of file
@@ -1 +1 @@
-./query.sql
\ No newline at end of file
+SELECT table_name FROM ./query.sql
\ No newline at end of file
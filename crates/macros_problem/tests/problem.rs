@@ -1,11 +1,12 @@
 // Adds definitions for `TestColumnLimitMissed`
 // and `TestPrimaryKeyMissed` problems,
 // annotated with the `#[problem]` attribute.
+use core::{Client, CustomProblem, Inspector, PostgresClient, Problem, Severity};
 use macros_fixture::*;
 
 #[test]
 fn expand_problem() {
-    let mut definitions: Vec<_> = inventory::iter::<Definition>().map(|p| p.clone()).collect();
+    let mut definitions: Vec<_> = inventory::iter::<Definition>().cloned().collect();
     definitions.sort_by_key(|def| def.name);
 
     assert_eq!(
@@ -36,32 +37,45 @@ fn expand_problem() {
                     TaggedField {
                         name: "scope_name",
                         ty: "Option < String >",
-                        desc: "The scope of the database table"
+                        desc: "The scope of the database table",
+                        default: None,
+                        column: Some("nspname"),
+                        op: None
                     },
                     TaggedField {
                         name: "table_name",
                         ty: "Option < String >",
-                        desc: "The name of the table"
+                        desc: "The name of the table",
+                        default: None,
+                        column: None,
+                        op: None
                     },
                 ],
                 limits: &[TaggedField {
                     name: "limit",
                     ty: "u32",
-                    desc: "The max number of chars allowed in the column"
+                    desc: "The max number of chars allowed in the column",
+                    default: Some("255"),
+                    column: None,
+                    op: None
                 },],
-                query: "SELECT \
-                            t.relnamespace::regnamespace AS scope_name, \
+                query: Some(
+                    "SELECT \
+                            n.nspname AS scope_name, \
                             a.attrelid::regclass AS table_name, \
                             a.attname AS column_name, \
                             {{ limit }} AS limit \
                         FROM pg_attribute a \
                             INNER JOIN pg_class t \
                                 ON a.attrelid = t.oid \
+                            INNER JOIN pg_namespace n \
+                                ON n.oid = t.relnamespace \
                             LEFT OUTER JOIN pg_constraint c \
                                 ON c.conrelid = a.attrelid \
                                 AND c.conkey = a.attnum \
                                 AND c.contype = 'p' \
                             WHERE c.contype IS NULL;",
+                ),
                 message: "The size of the {{ scope_name }}.{{ table_name }}.{{ column_name }} \
                           is not restricted to {{ limit }} chars.",
                 migration: Some(
@@ -73,6 +87,8 @@ fn expand_problem() {
                     "ALTER TABLE {{ scope_name }}.{{ table_name }} \
                     DROP CONSTRAINT {{ column_name }}_limit;",
                 ),
+                severity: "error",
+                since: Some("0.1.0"),
             },
             Definition {
                 client: "PostgresClient",
@@ -91,25 +107,123 @@ fn expand_problem() {
                     TaggedField {
                         name: "scope_name",
                         ty: "Option < String >",
-                        desc: "The scope of the database table"
+                        desc: "The scope of the database table",
+                        default: None,
+                        column: None,
+                        op: None
                     },
                     TaggedField {
                         name: "table_name",
                         ty: "Option < String >",
-                        desc: "The name of the table"
+                        desc: "The name of the table",
+                        default: None,
+                        column: None,
+                        op: None
                     },
                 ],
                 limits: &[],
-                query: "SELECT c.relnamespace::regnamespace AS scope_name, \
+                query: Some(
+                    "SELECT c.relnamespace::regnamespace AS scope_name, \
                             c.relname AS table_name \
                         FROM pg_catalog.pg_class c \
                            LEFT OUTER JOIN pg_catalog.pg_index i \
                                ON c.oid = i.indrelid AND i.indisprimary \
                         WHERE i.indkey IS NULL;",
+                ),
                 message: "Index {{ scope_name }}.{{ table_name }} is missed.",
                 migration: None,
                 rollback: None,
+                severity: "warning",
+                since: None,
             },
         ],
     );
 }
+
+#[test]
+fn keep_problem_struct() {
+    let problem: TestPrimaryKeyMissed =
+        serde_json::from_str(r#"{"scope_name": "public", "table_name": "users"}"#).unwrap();
+
+    assert_eq!(problem.scope_name, "public");
+    assert_eq!(problem.table_name, "users");
+}
+
+#[test]
+fn generate_custom_problem() {
+    let problem = TestPrimaryKeyMissed {
+        scope_name: "public".to_string(),
+        table_name: "users".to_string(),
+    };
+
+    assert_eq!(TestPrimaryKeyMissed::kind_(), "TestPrimaryKeyMissed");
+    assert_eq!(problem.message().unwrap(), "Index public.users is missed.");
+    assert!(problem.migration().is_none());
+    assert_eq!(problem.severity(), Severity::Warning);
+}
+
+#[test]
+fn generate_client_check() {
+    let inspector = TestPrimaryKeyMissedInspector::build("TestPrimaryKeyMissed", "{}").unwrap();
+
+    assert!(TestPrimaryKeyMissed::applies_to_client(
+        PostgresClient::NAME
+    ));
+    assert!(inspector.applies_to_client(PostgresClient::NAME));
+    assert!(!TestPrimaryKeyMissed::applies_to_client("OtherClient"));
+    assert!(!inspector.applies_to_client("OtherClient"));
+}
+
+#[test]
+fn generate_inspector() {
+    let config = r#"{"only": [{"table_name": "users"}]}"#;
+    let inspector = TestPrimaryKeyMissedInspector::build("TestPrimaryKeyMissed", config).unwrap();
+
+    let query = inspector.query().unwrap();
+
+    assert!(query.starts_with("SELECT c.relnamespace::regnamespace AS scope_name"));
+    assert!(query.ends_with("table_name = 'users';"));
+}
+
+#[test]
+fn generate_inspector_with_column() {
+    let config = r#"{"limit": 255, "only": [{"scope_name": "public"}]}"#;
+    let inspector = TestColumnLimitMissedInspector::build("TestColumnLimitMissed", config).unwrap();
+    let query = inspector.query().unwrap();
+
+    assert!(query.ends_with("nspname = 'public';"));
+}
+
+#[test]
+fn generate_problem_kind_enum() {
+    let target = quote::quote! {
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        pub enum ProblemKind {
+            TestColumnLimitMissed,
+            TestPrimaryKeyMissed,
+        }
+
+        impl ProblemKind {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    Self::TestColumnLimitMissed => "TestColumnLimitMissed",
+                    Self::TestPrimaryKeyMissed => "TestPrimaryKeyMissed",
+                }
+            }
+        }
+
+        impl std::str::FromStr for ProblemKind {
+            type Err = String;
+
+            fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+                match s {
+                    "TestColumnLimitMissed" => Ok(Self::TestColumnLimitMissed),
+                    "TestPrimaryKeyMissed" => Ok(Self::TestPrimaryKeyMissed),
+                    _ => Err(s.to_string()),
+                }
+            }
+        }
+    };
+
+    assert_eq!(problem_kind_enum().unwrap(), target.to_string());
+}